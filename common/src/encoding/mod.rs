@@ -22,6 +22,36 @@ impl Display for EncodingError {
     }
 }
 
+impl Error for EncodingError {}
+
+#[derive(Debug)]
+pub enum EncodeStringError {
+    InvalidChar { invalid: char, index: usize },
+    TooLong { length: usize, max_length: usize },
+}
+
+impl Display for EncodeStringError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            EncodeStringError::InvalidChar { invalid, index } => write!(
+                f,
+                "Character {:?} at position {} can't be encoded",
+                invalid, index
+            ),
+            EncodeStringError::TooLong { length, max_length } => write!(
+                f,
+                "String of length {} is longer than the maximum of {}",
+                length, max_length
+            ),
+        }
+    }
+}
+
+impl Error for EncodeStringError {}
+
+/// Byte marking the end of a string, the game also uses it to pad fixed-size string buffers.
+pub const STRING_TERMINATOR: u8 = 0xFF;
+
 pub fn parse_string(raw: &[u8]) -> Result<String, EncodingError> {
     enum InternalError {
         Encoding(EncodingError),
@@ -71,3 +101,27 @@ pub fn parse_string_lossy(mut raw: &[u8]) -> String {
     }
     result
 }
+
+/// Inverse of `parse_string`, fills the whole buffer with the encoded string followed by
+/// terminators. Strings as long as the buffer are stored without a terminator, like the game does.
+pub fn encode_string(s: &str, buffer: &mut [u8]) -> Result<(), EncodeStringError> {
+    let length = s.chars().count();
+    if length > buffer.len() {
+        return Err(EncodeStringError::TooLong {
+            length,
+            max_length: buffer.len(),
+        });
+    }
+    for (index, c) in s.chars().enumerate() {
+        // Some characters are mapped more than once, the regular ones come last in the charmap
+        let encoded = CHARMAP
+            .iter()
+            .rposition(|e| matches!(e, Encoding::Char(mapped) if *mapped == c))
+            .ok_or(EncodeStringError::InvalidChar { invalid: c, index })?;
+        buffer[index] = encoded as u8;
+    }
+    for b in &mut buffer[length..] {
+        *b = STRING_TERMINATOR;
+    }
+    Ok(())
+}
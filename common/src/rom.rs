@@ -53,6 +53,29 @@ pub enum Split {
     Status,
 }
 
+/// How much experience a species needs to reach each level, in the order the games index them.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum GrowthRate {
+    MediumFast,
+    Erratic,
+    Fluctuating,
+    MediumSlow,
+    Fast,
+    Slow,
+}
+
+/// Chance of a species being female, the raw values 0, 254 and 255 are special cased.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum GenderRatio {
+    AlwaysMale,
+    AlwaysFemale,
+    Genderless,
+    /// Female if the lowest byte of the personality value is below the threshold
+    Mixed {
+        female_threshold: u8,
+    },
+}
+
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum ItemPocket {
     Items,
@@ -97,6 +120,20 @@ pub struct Rom {
     pub items: Vec<Item>,
     pub moves: Vec<Move>,
     pub species: Vec<Species>,
+    /// Maps by group, then by number within the group
+    #[serde(default)]
+    pub maps: Vec<Vec<Map>>,
+}
+
+impl Rom {
+    /// Items start at id 1, the NONE placeholder isn't stored.
+    pub fn item(&self, id: ItemId) -> Option<&Item> {
+        self.items.get(id.get() as usize - 1)
+    }
+
+    pub fn map(&self, group: u8, number: u8) -> Option<&Map> {
+        self.maps.get(group as usize)?.get(number as usize)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -112,6 +149,14 @@ pub struct Item {
     pub item_type: ItemType,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Map {
+    /// Region map section the map belongs to
+    pub section: u8,
+    /// Name of the section, e.g. "ROUTE 4", `None` for sections outside of Kanto
+    pub name: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Move {
     pub name: String,
@@ -139,10 +184,11 @@ pub struct Species {
     pub hold_item1: Option<ItemId>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub hold_item2: Option<ItemId>,
-    pub gender_ratio: u8,
+    pub gender_ratio: GenderRatio,
+    /// Each cycle takes `EGG_CYCLE_STEPS` steps, see `hatch_steps`
     pub egg_cycles: u8,
     pub base_friendship: u8,
-    pub growth_rate: u8,
+    pub growth_rate: GrowthRate,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub egg_group1: Option<EggGroup>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -155,7 +201,122 @@ pub struct Species {
     pub hidden_ability: Option<AbilityId>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Steps the player has to take for an egg to go through one cycle.
+pub const EGG_CYCLE_STEPS: u32 = 256;
+
+impl GenderRatio {
+    pub fn from_raw(raw: u8) -> Self {
+        match raw {
+            0 => GenderRatio::AlwaysMale,
+            254 => GenderRatio::AlwaysFemale,
+            255 => GenderRatio::Genderless,
+            female_threshold => GenderRatio::Mixed { female_threshold },
+        }
+    }
+
+    pub fn to_raw(self) -> u8 {
+        match self {
+            GenderRatio::AlwaysMale => 0,
+            GenderRatio::AlwaysFemale => 254,
+            GenderRatio::Genderless => 255,
+            GenderRatio::Mixed { female_threshold } => female_threshold,
+        }
+    }
+
+    /// Whether a Pokémon with the given personality value is female, `None` if genderless.
+    pub fn is_female(self, personality: u32) -> Option<bool> {
+        match self {
+            GenderRatio::AlwaysMale => Some(false),
+            GenderRatio::AlwaysFemale => Some(true),
+            GenderRatio::Genderless => None,
+            GenderRatio::Mixed { female_threshold } => {
+                Some((personality & 0xFF) < female_threshold as u32)
+            }
+        }
+    }
+}
+
+impl Species {
+    /// Steps for an egg of this species to hatch. The counter is only checked every
+    /// `EGG_CYCLE_STEPS` steps and the egg hatches on the check after its cycles run out.
+    pub fn hatch_steps(&self) -> u32 {
+        egg_cycles_to_steps(self.egg_cycles)
+    }
+}
+
+/// Steps for an egg with the given cycles left to hatch.
+pub fn egg_cycles_to_steps(cycles: u8) -> u32 {
+    (cycles as u32 + 1) * EGG_CYCLE_STEPS
+}
+
+pub const MAX_LEVEL: u8 = 100;
+
+/// Experience needed for each level from 0 to 100, per growth rate.
+const EXPERIENCE_TABLES: [[u32; MAX_LEVEL as usize + 1]; 6] = [
+    experience_table(GrowthRate::MediumFast),
+    experience_table(GrowthRate::Erratic),
+    experience_table(GrowthRate::Fluctuating),
+    experience_table(GrowthRate::MediumSlow),
+    experience_table(GrowthRate::Fast),
+    experience_table(GrowthRate::Slow),
+];
+
+const fn experience_table(growth_rate: GrowthRate) -> [u32; MAX_LEVEL as usize + 1] {
+    let mut table = [0; MAX_LEVEL as usize + 1];
+    // Levels 0 and 1 need no experience
+    let mut level = 2;
+    while level <= MAX_LEVEL as usize {
+        let n = level as i64;
+        let cube = n * n * n;
+        table[level] = match growth_rate {
+            GrowthRate::MediumFast => cube,
+            GrowthRate::Erratic if n <= 50 => cube * (100 - n) / 50,
+            GrowthRate::Erratic if n <= 68 => cube * (150 - n) / 100,
+            GrowthRate::Erratic if n <= 98 => cube * ((1911 - 10 * n) / 3) / 500,
+            GrowthRate::Erratic => cube * (160 - n) / 100,
+            GrowthRate::Fluctuating if n <= 15 => cube * ((n + 1) / 3 + 24) / 50,
+            GrowthRate::Fluctuating if n <= 36 => cube * (n + 14) / 50,
+            GrowthRate::Fluctuating => cube * (n / 2 + 32) / 50,
+            GrowthRate::MediumSlow => 6 * cube / 5 - 15 * n * n + 100 * n - 140,
+            GrowthRate::Fast => 4 * cube / 5,
+            GrowthRate::Slow => 5 * cube / 4,
+        } as u32;
+        level += 1;
+    }
+    table
+}
+
+impl GrowthRate {
+    /// Experience needed for each level from 0 to `MAX_LEVEL`.
+    pub fn experience_table(self) -> &'static [u32; MAX_LEVEL as usize + 1] {
+        &EXPERIENCE_TABLES[self as usize]
+    }
+
+    /// Minimum experience for the given level, capped at `MAX_LEVEL`.
+    pub fn exp_for_level(self, level: u8) -> u32 {
+        self.experience_table()[level.min(MAX_LEVEL) as usize]
+    }
+
+    pub fn level_for_exp(self, exp: u32) -> u8 {
+        let table = self.experience_table();
+        (1..=MAX_LEVEL)
+            .rev()
+            .find(|level| table[*level as usize] <= exp)
+            .unwrap_or(1)
+    }
+
+    /// Experience left to reach the next level, `None` at `MAX_LEVEL`.
+    pub fn exp_to_next_level(self, exp: u32) -> Option<u32> {
+        let level = self.level_for_exp(exp);
+        if level >= MAX_LEVEL {
+            None
+        } else {
+            Some(self.exp_for_level(level + 1) - exp)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Stats<T> {
     pub hp: T,
     pub atk: T,
@@ -164,3 +325,37 @@ pub struct Stats<T> {
     pub spd: T,
     pub spe: T,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn experience_tables() {
+        assert_eq!(GrowthRate::Erratic.exp_for_level(100), 600_000);
+        assert_eq!(GrowthRate::Fluctuating.exp_for_level(100), 1_640_000);
+        assert_eq!(GrowthRate::MediumSlow.exp_for_level(2), 9);
+        assert_eq!(GrowthRate::MediumFast.exp_for_level(100), 1_000_000);
+        assert_eq!(GrowthRate::Fast.exp_for_level(100), 800_000);
+        assert_eq!(GrowthRate::Slow.exp_for_level(100), 1_250_000);
+        assert_eq!(GrowthRate::Slow.exp_for_level(1), 0);
+        // Levels past the cap use the level 100 experience
+        assert_eq!(GrowthRate::MediumFast.exp_for_level(255), 1_000_000);
+    }
+
+    #[test]
+    fn level_boundaries() {
+        let rate = GrowthRate::MediumFast;
+        assert_eq!(rate.level_for_exp(0), 1);
+        assert_eq!(rate.level_for_exp(7), 1);
+        assert_eq!(rate.level_for_exp(8), 2);
+        assert_eq!(rate.level_for_exp(999), 9);
+        assert_eq!(rate.level_for_exp(1000), 10);
+        assert_eq!(rate.level_for_exp(u32::MAX), MAX_LEVEL);
+        assert_eq!(rate.exp_to_next_level(0), Some(8));
+        assert_eq!(rate.exp_to_next_level(999), Some(1));
+        assert_eq!(rate.exp_to_next_level(1000), Some(331));
+        assert_eq!(rate.exp_to_next_level(999_999), Some(1));
+        assert_eq!(rate.exp_to_next_level(1_000_000), None);
+    }
+}
@@ -63,7 +63,6 @@ fn map_item_type(raw: u8) -> Result<ItemType, ValidationError> {
         0x01 => Ok(ItemType::HealthRecovery),
         0x02 => Ok(ItemType::StatusRecovery),
         0x03 => Ok(ItemType::PpRecovery),
-        0x03 => Ok(ItemType::StatBoostDrink),
         0x04 => Ok(ItemType::StatBoostWing),
         0x05 => Ok(ItemType::EvolutionStone),
         0x06 => Ok(ItemType::EvolutionItem),
@@ -0,0 +1,151 @@
+use std::io::{self, Read, Seek, SeekFrom};
+
+use byteorder::ReadBytesExt;
+
+use poke3_common::encoding::parse_string;
+use poke3_common::rom::Map;
+
+use super::{ReadRomResult, ValidationError};
+use crate::read::{ReadTableError, RomReadExt};
+
+/// Address of the pointer to `gMapGroups`, an array of pointers to each group's array of map
+/// header pointers.
+const MAP_GROUPS_OFFSET: u64 = 0x0005_524C;
+const MAP_GROUPS_COUNT: usize = 43;
+/// Address of the pointer to `sMapNames`, the names of the Kanto region map sections.
+const MAP_NAMES_OFFSET: u64 = 0x000C_0C94;
+const MAPSECS_KANTO: u8 = 0x58;
+const MAPSEC_NONE: u8 = 0xC5;
+const MAP_HEADER_SECTION_OFFSET: u64 = 0x14;
+const MAP_NAME_LENGTH: usize = 19;
+
+/// Reads the header of every map, grouped like in `gMapGroups`. Groups have no terminator, but
+/// the vanilla ROM stores their arrays back to back right before `gMapGroups`, so each group
+/// ends where the next array or the table itself starts.
+pub fn read_maps<R: Read + Seek>(reader: &mut R) -> ReadRomResult<Vec<Vec<Map>>> {
+    let table_err = |table, index| move |err| ReadTableError { table, index, err };
+
+    let table_address = reader
+        .seek(SeekFrom::Start(MAP_GROUPS_OFFSET))
+        .and_then(|_| reader.read_pointer())
+        .and_then(|address| reader.seek(SeekFrom::Start(address)))
+        .map_err(table_err("gMapGroups", 0))?;
+    let groups = (0..MAP_GROUPS_COUNT)
+        .map(|i| reader.read_pointer().map_err(table_err("gMapGroups", i)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let names = read_map_names(reader)?;
+
+    let mut bounds: Vec<_> = groups.iter().copied().chain(Some(table_address)).collect();
+    bounds.sort_unstable();
+    groups
+        .iter()
+        .enumerate()
+        .map(|(i, &group)| {
+            let end = bounds
+                .iter()
+                .copied()
+                .find(|&bound| bound > group)
+                .unwrap_or(table_address);
+            let count = ((end.saturating_sub(group) / 4) as usize).min(u8::MAX as usize + 1);
+            (0..count)
+                .map(|number| {
+                    let map_err = table_err("gMapGroups", i);
+                    reader
+                        .seek(SeekFrom::Start(group + number as u64 * 4))
+                        .map_err(map_err)?;
+                    let header = reader.read_pointer().map_err(map_err)?;
+                    reader
+                        .seek(SeekFrom::Start(header + MAP_HEADER_SECTION_OFFSET))
+                        .map_err(map_err)?;
+                    let section = reader.read_u8().map_err(map_err)?;
+                    let name = section
+                        .checked_sub(MAPSECS_KANTO)
+                        .and_then(|index| names.get(index as usize))
+                        .cloned();
+                    Ok(Map { section, name })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn read_map_names<R: Read + Seek>(reader: &mut R) -> ReadRomResult<Vec<String>> {
+    let table_err = |index| {
+        move |err| ReadTableError {
+            table: "sMapNames",
+            index,
+            err,
+        }
+    };
+    reader
+        .seek_pointer_at(MAP_NAMES_OFFSET)
+        .map_err(table_err(0))?;
+    let pointers = (0..(MAPSEC_NONE - MAPSECS_KANTO) as usize)
+        .map(|i| reader.read_pointer().map_err(table_err(i)))
+        .collect::<Result<Vec<_>, _>>()?;
+    pointers
+        .into_iter()
+        .enumerate()
+        .map(|(i, pointer)| {
+            let raw = read_bytes_at(reader, pointer).map_err(table_err(i))?;
+            Ok(parse_string(&raw).map_err(|e| ValidationError::from_display("map name", e))?)
+        })
+        .collect()
+}
+
+fn read_bytes_at<R: Read + Seek>(
+    reader: &mut R,
+    address: u64,
+) -> io::Result<[u8; MAP_NAME_LENGTH]> {
+    let mut raw = [0u8; MAP_NAME_LENGTH];
+    reader.seek(SeekFrom::Start(address))?;
+    reader.read_exact(&mut raw)?;
+    Ok(raw)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use byteorder::{ByteOrder, LittleEndian};
+
+    use super::*;
+
+    fn write_pointer(rom: &mut [u8], at: usize, address: usize) {
+        LittleEndian::write_u32(&mut rom[at..], 0x0800_0000 + address as u32);
+    }
+
+    #[test]
+    fn read_grouped_maps() {
+        let mut rom = vec![0u8; 0x0010_0000];
+        // Two map headers, in Pallet Town and outside of Kanto
+        let headers = [0x0008_0000, 0x0008_0100];
+        rom[headers[0] + 0x14] = MAPSECS_KANTO;
+        rom[headers[1] + 0x14] = 0x10;
+        // Group 0 holds both maps and every other group is empty, the table follows the groups
+        let group0 = 0x0009_0000;
+        write_pointer(&mut rom, group0, headers[0]);
+        write_pointer(&mut rom, group0 + 4, headers[1]);
+        let table = group0 + 8;
+        for i in 0..MAP_GROUPS_COUNT {
+            write_pointer(&mut rom, table + i * 4, if i == 0 { group0 } else { table });
+        }
+        write_pointer(&mut rom, MAP_GROUPS_OFFSET as usize, table);
+
+        let names = 0x000A_0000;
+        let pallet_town = 0x000A_1000;
+        rom[pallet_town..pallet_town + 3].copy_from_slice(&[0xCA, 0xBB, 0xFF]); // "PA"
+        for i in 0..(MAPSEC_NONE - MAPSECS_KANTO) as usize {
+            write_pointer(&mut rom, names + i * 4, pallet_town);
+        }
+        write_pointer(&mut rom, MAP_NAMES_OFFSET as usize, names);
+
+        let maps = read_maps(&mut Cursor::new(rom)).unwrap();
+        assert_eq!(maps.len(), MAP_GROUPS_COUNT);
+        assert_eq!(maps[0].len(), 2);
+        assert_eq!(maps[0][0].name.as_deref(), Some("PA"));
+        assert_eq!(maps[0][1].section, 0x10);
+        assert_eq!(maps[0][1].name, None);
+        assert!(maps[1..].iter().all(Vec::is_empty));
+    }
+}
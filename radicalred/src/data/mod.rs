@@ -1,9 +1,11 @@
 mod abilities;
 mod items;
+mod maps;
 mod species;
 
 use std::convert::TryInto;
-use std::fmt::Display;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
 use std::io::{Read, Seek};
 
 use poke3_common::rom::Rom;
@@ -35,6 +37,14 @@ impl ValidationError {
     }
 }
 
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Invalid {}: {}", self.field, self.message)
+    }
+}
+
+impl Error for ValidationError {}
+
 #[derive(Debug)]
 pub enum ReadRomError {
     ReadTable(ReadTableError),
@@ -43,6 +53,24 @@ pub enum ReadRomError {
 
 pub type ReadRomResult<T> = Result<T, ReadRomError>;
 
+impl Display for ReadRomError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ReadRomError::ReadTable(err) => err.fmt(f),
+            ReadRomError::Validation(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for ReadRomError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ReadRomError::ReadTable(err) => Some(err),
+            ReadRomError::Validation(err) => Some(err),
+        }
+    }
+}
+
 impl From<ReadTableError> for ReadRomError {
     fn from(err: ReadTableError) -> Self {
         ReadRomError::ReadTable(err)
@@ -56,11 +84,13 @@ impl From<ValidationError> for ReadRomError {
 }
 
 pub fn read_rom<R: Read + Seek>(mut reader: R) -> ReadRomResult<Rom> {
+    let maps = maps::read_maps(&mut reader)?;
     let rom = Rom {
         abilities: try_into_iter(reader.read_table::<RawAbilityName>()?)?,
         items: try_into_iter(reader.read_table::<RawItem>()?)?,
         moves: Vec::new(),
         species: try_into_iter(RawPokemon::read_all(&mut reader)?)?,
+        maps,
     };
     Ok(rom)
 }
@@ -3,7 +3,9 @@ use std::convert::TryFrom;
 use bitfield::bitfield;
 
 use poke3_common::encoding::parse_string;
-use poke3_common::rom::{AbilityId, EggGroup, ItemId, PokemonType, Species, Stats};
+use poke3_common::rom::{
+    AbilityId, EggGroup, GenderRatio, GrowthRate, ItemId, PokemonType, Species, Stats,
+};
 
 use super::ValidationError;
 use crate::read::{FromTable, Named};
@@ -99,10 +101,10 @@ impl TryFrom<RawPokemon> for Species {
             },
             hold_item1: ItemId::new(raw.value.item1),
             hold_item2: ItemId::new(raw.value.item2),
-            gender_ratio: raw.value.gender_ratio,
+            gender_ratio: GenderRatio::from_raw(raw.value.gender_ratio),
             egg_cycles: raw.value.egg_cycles,
             base_friendship: raw.value.friendship,
-            growth_rate: raw.value.growth_rate,
+            growth_rate: map_growth_rate(raw.value.growth_rate)?,
             egg_group1: map_egg_group("egg_group1", raw.value.egg_group1)?,
             egg_group2: if raw.value.egg_group1 == raw.value.egg_group2 {
                 None
@@ -136,7 +138,22 @@ fn map_pokemon_type(raw: u8) -> PokemonType {
         0x10 => PokemonType::Dragon,
         0x11 => PokemonType::Dark,
         0x17 => PokemonType::Fairy,
-        x => PokemonType::Unknown,
+        _ => PokemonType::Unknown,
+    }
+}
+
+fn map_growth_rate(raw: u8) -> Result<GrowthRate, ValidationError> {
+    match raw {
+        0 => Ok(GrowthRate::MediumFast),
+        1 => Ok(GrowthRate::Erratic),
+        2 => Ok(GrowthRate::Fluctuating),
+        3 => Ok(GrowthRate::MediumSlow),
+        4 => Ok(GrowthRate::Fast),
+        5 => Ok(GrowthRate::Slow),
+        other => Err(ValidationError::new(
+            "growth_rate",
+            format!("Invalid growth rate {}", other),
+        )),
     }
 }
 
@@ -73,7 +73,7 @@ pub trait RomReadExt: Sized {
     fn read_pointer(&mut self) -> io::Result<u64>;
     fn seek_pointer(&mut self) -> io::Result<()>;
     fn seek_pointer_at(&mut self, address: u64) -> io::Result<()>;
-    fn read_table<T: FromTable>(&mut self) -> ReadTableResult<TableReader<T, Self>>;
+    fn read_table<T: FromTable>(&mut self) -> ReadTableResult<TableReader<'_, T, Self>>;
 }
 
 impl<R: Read + Seek> RomReadExt for R {
@@ -95,7 +95,7 @@ impl<R: Read + Seek> RomReadExt for R {
         self.seek_pointer()
     }
 
-    fn read_table<T: FromTable>(&mut self) -> ReadTableResult<TableReader<T, Self>> {
+    fn read_table<T: FromTable>(&mut self) -> ReadTableResult<TableReader<'_, T, Self>> {
         self.seek_pointer_at(T::OFFSET)
             .map_err(|e| ReadTableError::new::<T>(0, e))?;
         // Skip first element, as it's always a placeholder for NONE
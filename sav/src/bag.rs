@@ -0,0 +1,127 @@
+use byteorder::{ByteOrder, LittleEndian};
+
+use poke3_common::rom::{Item, ItemId, ItemPocket, Rom};
+
+use crate::layout::{BagLayout, PocketLayout};
+use crate::{WriteSaveError, WriteSaveResult};
+
+const ITEM_SLOT_SIZE: usize = 4;
+
+/// Items in the player's bag, each pocket holds its non-empty slots in order.
+#[derive(Clone, Debug, Default)]
+pub struct Bag {
+    pub items: Vec<ItemSlot>,
+    pub key_items: Vec<ItemSlot>,
+    pub poke_balls: Vec<ItemSlot>,
+    pub tm_case: Vec<ItemSlot>,
+    pub berry_pouch: Vec<ItemSlot>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ItemSlot {
+    pub item: ItemId,
+    pub quantity: u16,
+}
+
+impl Bag {
+    /// Bag quantities are XORed with the lower 16 bits of the save's security key.
+    pub fn from_bytes(data: &[u8], layout: &BagLayout, key: u16) -> Self {
+        Bag {
+            items: read_pocket(data, &layout.items, key),
+            key_items: read_pocket(data, &layout.key_items, key),
+            poke_balls: read_pocket(data, &layout.poke_balls, key),
+            tm_case: read_pocket(data, &layout.tm_case, key),
+            berry_pouch: read_pocket(data, &layout.berry_pouch, key),
+        }
+    }
+
+    pub fn write_bytes(
+        &self,
+        data: &mut [u8],
+        layout: &BagLayout,
+        key: u16,
+    ) -> WriteSaveResult<()> {
+        for &pocket in &[
+            ItemPocket::Items,
+            ItemPocket::KeyItems,
+            ItemPocket::PokeBalls,
+            ItemPocket::TmCase,
+            ItemPocket::BerryPouch,
+        ] {
+            write_pocket(
+                data,
+                layout.pocket(pocket),
+                self.pocket(pocket),
+                key,
+                &format!("{:?} pocket", pocket),
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn pocket(&self, pocket: ItemPocket) -> &Vec<ItemSlot> {
+        match pocket {
+            ItemPocket::Items => &self.items,
+            ItemPocket::KeyItems => &self.key_items,
+            ItemPocket::PokeBalls => &self.poke_balls,
+            ItemPocket::TmCase => &self.tm_case,
+            ItemPocket::BerryPouch => &self.berry_pouch,
+        }
+    }
+
+    pub fn pocket_mut(&mut self, pocket: ItemPocket) -> &mut Vec<ItemSlot> {
+        match pocket {
+            ItemPocket::Items => &mut self.items,
+            ItemPocket::KeyItems => &mut self.key_items,
+            ItemPocket::PokeBalls => &mut self.poke_balls,
+            ItemPocket::TmCase => &mut self.tm_case,
+            ItemPocket::BerryPouch => &mut self.berry_pouch,
+        }
+    }
+}
+
+impl ItemSlot {
+    /// Item data for this slot, `None` if the ROM has no item with this id.
+    pub fn resolve<'a>(&self, rom: &'a Rom) -> Option<&'a Item> {
+        rom.item(self.item)
+    }
+}
+
+/// Reads the non-empty slots of an item list. PC items aren't encrypted, so they use a key of 0.
+pub fn read_pocket(data: &[u8], layout: &PocketLayout, key: u16) -> Vec<ItemSlot> {
+    data[layout.offset..layout.offset + layout.capacity * ITEM_SLOT_SIZE]
+        .chunks(ITEM_SLOT_SIZE)
+        .filter_map(|raw| {
+            ItemId::new(LittleEndian::read_u16(raw)).map(|item| ItemSlot {
+                item,
+                quantity: LittleEndian::read_u16(&raw[2..]) ^ key,
+            })
+        })
+        .collect()
+}
+
+/// Writes the slots of an item list, clearing the remaining ones like the game does.
+pub fn write_pocket(
+    data: &mut [u8],
+    layout: &PocketLayout,
+    slots: &[ItemSlot],
+    key: u16,
+    name: &str,
+) -> WriteSaveResult<()> {
+    if slots.len() > layout.capacity {
+        return Err(WriteSaveError::InvalidData(format!(
+            "{} has {} slots, expected at most {}",
+            name,
+            slots.len(),
+            layout.capacity
+        )));
+    }
+    let raw_slots = data[layout.offset..layout.offset + layout.capacity * ITEM_SLOT_SIZE]
+        .chunks_mut(ITEM_SLOT_SIZE);
+    for (i, raw) in raw_slots.enumerate() {
+        let (item, quantity) = slots.get(i).map_or((0, 0), |s| (s.item.get(), s.quantity));
+        LittleEndian::write_u16(raw, item);
+        LittleEndian::write_u16(&mut raw[2..], quantity ^ key);
+    }
+    Ok(())
+}
@@ -0,0 +1,221 @@
+use std::fmt::{self, Debug, Formatter};
+use std::io::{Read, Seek, Write};
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::layout::Layout;
+use crate::sector::{Sector, SECTOR_DATA_SIZE, SECTOR_SIGNATURE};
+use crate::version::GameVersion;
+use crate::{LoadSaveError, LoadSaveResult, WriteSaveResult};
+
+pub const SAVE_SECTION_SECTORS: u8 = 14;
+
+/// Size of the chunk of a save structure each sector holds, structures bigger than this are split
+/// across consecutive sector ids.
+pub const SECTOR_CHUNK_SIZE: usize = 0xF80;
+pub const POKEMON_STORAGE_SIZE: usize = 0x83D0;
+
+/// Save structures of a save slot, reassembled from the sectors holding them.
+#[derive(Clone)]
+pub struct SaveBlocks {
+    pub save_block_2: Vec<u8>,
+    pub save_block_1: Vec<u8>,
+    pub pokemon_storage: Vec<u8>,
+    pub version: GameVersion,
+    /// Which of the two save slots this was read from
+    pub slot: u8,
+    /// Incremented by the game on every save
+    pub counter: u32,
+    /// Position of the sector with id 0 in the slot, the game shifts it by one on every save
+    pub rotation: u8,
+    /// Original sectors ordered by id, kept to preserve data outside of the save structures
+    sectors: Vec<Sector>,
+}
+
+impl Debug for SaveBlocks {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("SaveBlocks")
+            .field("version", &self.version)
+            .field("slot", &self.slot)
+            .field("counter", &self.counter)
+            .field("rotation", &self.rotation)
+            .finish()
+    }
+}
+
+impl SaveBlocks {
+    /// Reads the most recent valid save slot. The game version is detected if not given.
+    pub fn read<R: Read + Seek>(
+        mut reader: R,
+        version: Option<GameVersion>,
+    ) -> LoadSaveResult<Self> {
+        // Every sector in a slot has the same counter, so the first one is enough to check which
+        // slot is the most recent
+        let sector_results = (
+            Sector::read_at(&mut reader, 0),
+            Sector::read_at(&mut reader, SAVE_SECTION_SECTORS),
+        );
+        match sector_results {
+            (Ok(s1), Ok(s2)) if s1.counter >= s2.counter => {
+                SaveBlocks::read_slot_with_fallback(reader, 0, version)
+            }
+            (Ok(_), Ok(_)) => SaveBlocks::read_slot_with_fallback(reader, 1, version),
+            (Ok(_), Err(_)) => SaveBlocks::read_slot(reader, 0, version),
+            (Err(_), Ok(_)) => SaveBlocks::read_slot(reader, 1, version),
+            (Err(e), _) => Err(e),
+        }
+    }
+
+    fn read_slot_with_fallback<R: Read + Seek>(
+        mut reader: R,
+        slot: u8,
+        version: Option<GameVersion>,
+    ) -> LoadSaveResult<Self> {
+        // If the most recent slot is corrupt the game loads the other one, so do the same. When
+        // both fail the most recent slot's error is the one explaining what went wrong.
+        SaveBlocks::read_slot(&mut reader, slot, version)
+            .or_else(|e| SaveBlocks::read_slot(reader, slot ^ 1, version).map_err(|_| e))
+    }
+
+    /// Reads all sectors in a save slot and joins them by id, regardless of the order they're
+    /// stored in.
+    pub fn read_slot<R: Read + Seek>(
+        mut reader: R,
+        slot: u8,
+        version: Option<GameVersion>,
+    ) -> LoadSaveResult<Self> {
+        let mut sectors: [Option<Sector>; SAVE_SECTION_SECTORS as usize] = Default::default();
+        let mut rotation = 0;
+        let mut counter = None;
+        for i in 0..SAVE_SECTION_SECTORS {
+            let index = slot * SAVE_SECTION_SECTORS + i;
+            let sector = Sector::read_at(&mut reader, index)?;
+            if sector.security != SECTOR_SIGNATURE {
+                return Err(LoadSaveError::InvalidSignature {
+                    sector: index,
+                    signature: sector.security,
+                });
+            }
+            let expected_counter = *counter.get_or_insert(sector.counter);
+            if sector.counter != expected_counter {
+                return Err(LoadSaveError::CounterMismatch {
+                    sector: index,
+                    expected: expected_counter,
+                    counter: sector.counter,
+                });
+            }
+            let entry =
+                sectors
+                    .get_mut(sector.id as usize)
+                    .ok_or(LoadSaveError::InvalidSectorId {
+                        sector: index,
+                        id: sector.id,
+                    })?;
+            if entry.is_some() {
+                return Err(LoadSaveError::DuplicateSectorId { id: sector.id });
+            }
+            if sector.id == 0 {
+                rotation = i;
+            }
+            *entry = Some(sector);
+        }
+        let sectors = sectors
+            .iter()
+            .enumerate()
+            .map(|(id, sector)| sector.ok_or(LoadSaveError::MissingSectorId { id: id as u16 }))
+            .collect::<LoadSaveResult<Vec<_>>>()?;
+
+        let version = match version {
+            Some(version) => version,
+            None => GameVersion::detect(&sectors)?,
+        };
+        let layout = version.layout();
+
+        let mut blocks = SaveBlocks {
+            save_block_2: vec![0; layout.save_block_2_size],
+            save_block_1: vec![0; layout.save_block_1_size],
+            pokemon_storage: vec![0; POKEMON_STORAGE_SIZE],
+            version,
+            slot,
+            counter: sectors[0].counter,
+            rotation,
+            sectors: Vec::new(),
+        };
+        for (id, sector) in sectors.iter().enumerate() {
+            let data = sector.validate_data(checksum_size(layout, id))?;
+            let chunk = blocks.chunk_mut(id);
+            let size = chunk.len();
+            chunk.copy_from_slice(&data[..size]);
+        }
+        blocks.sectors = sectors;
+        Ok(blocks)
+    }
+
+    /// Key money, coins and bag quantities are XORed with, 0 for games that don't encrypt them.
+    /// Quantities only use the lower 16 bits.
+    pub fn security_key(&self) -> u32 {
+        match self.version.layout().security_key {
+            Some(offset) => LittleEndian::read_u32(&self.save_block_2[offset..]),
+            None => 0,
+        }
+    }
+
+    /// Moves to the slot the game would write the next save to, bumping the counter and rotating
+    /// the sectors.
+    pub fn advance(&mut self) {
+        self.slot ^= 1;
+        self.counter = self.counter.wrapping_add(1);
+        self.rotation = (self.rotation + 1) % SAVE_SECTION_SECTORS;
+    }
+
+    /// Writes every sector of the slot, recalculating their checksums.
+    pub fn write<W: Write + Seek>(&self, mut writer: W) -> WriteSaveResult<()> {
+        let layout = self.version.layout();
+        for id in 0..SAVE_SECTION_SECTORS {
+            let mut data = *self.sectors[id as usize].data();
+            let chunk = self.chunk(id as usize);
+            data[..chunk.len()].copy_from_slice(chunk);
+            let checksum_size = checksum_size(layout, id as usize);
+            let sector = Sector::new(id as u16, data, checksum_size, self.counter);
+            let position = (id + self.rotation) % SAVE_SECTION_SECTORS;
+            sector.write_at(&mut writer, self.slot * SAVE_SECTION_SECTORS + position)?;
+        }
+        Ok(())
+    }
+
+    /// Part of the save structures stored in the sector with the given id.
+    fn chunk(&self, id: usize) -> &[u8] {
+        let (buffer, index) = match id {
+            0 => (&self.save_block_2, 0),
+            1..=4 => (&self.save_block_1, id - 1),
+            _ => (&self.pokemon_storage, id - 5),
+        };
+        &buffer[chunk_range(buffer.len(), index)]
+    }
+
+    fn chunk_mut(&mut self, id: usize) -> &mut [u8] {
+        let (buffer, index) = match id {
+            0 => (&mut self.save_block_2, 0),
+            1..=4 => (&mut self.save_block_1, id - 1),
+            _ => (&mut self.pokemon_storage, id - 5),
+        };
+        let range = chunk_range(buffer.len(), index);
+        &mut buffer[range]
+    }
+}
+
+fn chunk_range(size: usize, index: usize) -> std::ops::Range<usize> {
+    let start = index * SECTOR_CHUNK_SIZE;
+    start..size.min(start + SECTOR_CHUNK_SIZE)
+}
+
+/// Amount of data covered by the checksum of the sector with the given id.
+pub fn checksum_size(layout: &Layout, id: usize) -> usize {
+    let (size, index) = match id {
+        0 => return layout.save_block_2_size,
+        _ if layout.full_sector_checksums => return SECTOR_DATA_SIZE,
+        1..=4 => (layout.save_block_1_size, id - 1),
+        _ => (POKEMON_STORAGE_SIZE, id - 5),
+    };
+    chunk_range(size, index).len()
+}
@@ -0,0 +1,93 @@
+use byteorder::{ByteOrder, LittleEndian};
+
+use poke3_common::rom::Species;
+
+use crate::pokemon::{BoxPokemon, PokemonFormat};
+use crate::WriteSaveResult;
+
+pub const DAYCARE_SLOTS: usize = 2;
+
+/// Each slot holds the Pokémon, the mail it was holding and the steps it walked.
+const DAYCARE_SLOT_SIZE: usize = 0x8C;
+const DAYCARE_STEPS_OFFSET: usize = 0x88;
+const OFFSPRING_PERSONALITY_OFFSET: usize = DAYCARE_SLOTS * DAYCARE_SLOT_SIZE;
+const STEP_COUNTER_OFFSET: usize = OFFSPRING_PERSONALITY_OFFSET + 4;
+
+#[derive(Clone, Debug)]
+pub struct DayCare {
+    pub pokemon: [Option<DayCarePokemon>; DAYCARE_SLOTS],
+    /// Personality value of the egg waiting to be picked up, 0 if there's none
+    pub offspring_personality: u32,
+    /// Step counter the egg chance and egg cycles are checked against, which the game does when it
+    /// reaches 255. It keeps counting and wraps around, so checks happen every 256 steps
+    pub step_counter: u8,
+}
+
+#[derive(Clone, Debug)]
+pub struct DayCarePokemon {
+    pub pokemon: BoxPokemon,
+    /// Steps walked since the Pokémon was deposited, each one is a point of experience
+    pub steps: u32,
+}
+
+impl DayCare {
+    pub fn from_bytes(data: &[u8], format: PokemonFormat) -> Self {
+        let mut pokemon = [None, None];
+        for (i, slot) in pokemon.iter_mut().enumerate() {
+            let raw = &data[i * DAYCARE_SLOT_SIZE..(i + 1) * DAYCARE_SLOT_SIZE];
+            if BoxPokemon::is_present(raw) {
+                *slot = Some(DayCarePokemon {
+                    pokemon: BoxPokemon::from_bytes(&raw[..BoxPokemon::SIZE], format),
+                    steps: LittleEndian::read_u32(&raw[DAYCARE_STEPS_OFFSET..]),
+                });
+            }
+        }
+        DayCare {
+            pokemon,
+            offspring_personality: LittleEndian::read_u32(&data[OFFSPRING_PERSONALITY_OFFSET..]),
+            step_counter: data[STEP_COUNTER_OFFSET],
+        }
+    }
+
+    pub fn write_bytes(&self, data: &mut [u8], format: PokemonFormat) -> WriteSaveResult<()> {
+        for (i, slot) in self.pokemon.iter().enumerate() {
+            let raw = &mut data[i * DAYCARE_SLOT_SIZE..(i + 1) * DAYCARE_SLOT_SIZE];
+            match slot {
+                Some(daycare_pokemon) => {
+                    daycare_pokemon
+                        .pokemon
+                        .write_bytes(&mut raw[..BoxPokemon::SIZE], format)?;
+                    LittleEndian::write_u32(
+                        &mut raw[DAYCARE_STEPS_OFFSET..],
+                        daycare_pokemon.steps,
+                    );
+                }
+                None => raw.iter_mut().for_each(|b| *b = 0),
+            }
+        }
+        LittleEndian::write_u32(
+            &mut data[OFFSPRING_PERSONALITY_OFFSET..],
+            self.offspring_personality,
+        );
+        data[STEP_COUNTER_OFFSET] = self.step_counter;
+        Ok(())
+    }
+
+    /// Whether the Day Care man is holding an egg.
+    pub fn egg_pending(&self) -> bool {
+        self.offspring_personality != 0
+    }
+}
+
+impl DayCarePokemon {
+    /// Level the Pokémon would have if withdrawn now.
+    pub fn withdraw_level(&self, species: &Species) -> u8 {
+        let experience = self.pokemon.experience.saturating_add(self.steps);
+        species.growth_rate.level_for_exp(experience)
+    }
+
+    /// Levels gained since the Pokémon was deposited.
+    pub fn levels_gained(&self, species: &Species) -> u8 {
+        self.withdraw_level(species) - self.pokemon.level(species)
+    }
+}
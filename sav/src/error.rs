@@ -0,0 +1,152 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::io;
+
+use poke3_common::encoding::EncodeStringError;
+
+use crate::pokemon::{ChecksumStatus, PokemonSlot};
+use crate::version::GameVersion;
+
+#[derive(Debug)]
+pub enum LoadSaveError {
+    CorruptData(String),
+    Io(String, io::Error),
+    /// The sector at the given position doesn't hold the security signature the game writes
+    InvalidSignature {
+        sector: u8,
+        signature: u32,
+    },
+    InvalidSectorId {
+        sector: u8,
+        id: u16,
+    },
+    DuplicateSectorId {
+        id: u16,
+    },
+    MissingSectorId {
+        id: u16,
+    },
+    /// Sectors in the same slot should all have been written by the same save
+    CounterMismatch {
+        sector: u8,
+        expected: u32,
+        counter: u32,
+    },
+    InvalidChecksum {
+        id: u16,
+        expected: u16,
+        stored: u16,
+    },
+    InvalidPokemon {
+        slot: PokemonSlot,
+        checksum: ChecksumStatus,
+    },
+    /// The save matches none or more than one of the supported games
+    UnknownGameVersion {
+        candidates: Vec<GameVersion>,
+    },
+}
+
+pub type LoadSaveResult<T> = Result<T, LoadSaveError>;
+
+impl LoadSaveError {
+    pub fn io<S: Into<String>>(msg: S, err: io::Error) -> Self {
+        LoadSaveError::Io(msg.into(), err)
+    }
+}
+
+impl Display for LoadSaveError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            LoadSaveError::CorruptData(msg) => write!(f, "Corrupt save data: {}", msg),
+            LoadSaveError::Io(msg, err) => {
+                write!(f, "I/O Error (maybe corrupt save file): {} ({})", msg, err)
+            }
+            LoadSaveError::InvalidSignature { sector, signature } => write!(
+                f,
+                "Invalid signature 0x{:08X} in sector {}",
+                signature, sector
+            ),
+            LoadSaveError::InvalidSectorId { sector, id } => {
+                write!(f, "Invalid ID {} in sector {}", id, sector)
+            }
+            LoadSaveError::DuplicateSectorId { id } => write!(f, "Duplicate sector ID {}", id),
+            LoadSaveError::MissingSectorId { id } => write!(f, "Missing sector ID {}", id),
+            LoadSaveError::CounterMismatch {
+                sector,
+                expected,
+                counter,
+            } => write!(
+                f,
+                "Sector {} has save counter {}, expected {}",
+                sector, counter, expected
+            ),
+            LoadSaveError::InvalidChecksum {
+                id,
+                expected,
+                stored,
+            } => write!(
+                f,
+                "Invalid checksum for sector ID {}, expected 0x{:04X}, got 0x{:04X}",
+                id, expected, stored
+            ),
+            LoadSaveError::InvalidPokemon { slot, checksum } => {
+                write!(f, "Invalid Pokémon at {}: {}", slot, checksum)
+            }
+            LoadSaveError::UnknownGameVersion { candidates } => write!(
+                f,
+                "Couldn't detect the game version (candidates: {:?}), set it explicitly",
+                candidates
+            ),
+        }
+    }
+}
+
+impl Error for LoadSaveError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LoadSaveError::Io(_, err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum WriteSaveError {
+    /// Save data that doesn't fit the game's structures, e.g. more than 6 party members
+    InvalidData(String),
+    Encoding(String, EncodeStringError),
+    Io(String, io::Error),
+}
+
+pub type WriteSaveResult<T> = Result<T, WriteSaveError>;
+
+impl WriteSaveError {
+    pub fn io<S: Into<String>>(msg: S, err: io::Error) -> Self {
+        WriteSaveError::Io(msg.into(), err)
+    }
+
+    pub fn encoding<S: Into<String>>(field: S, err: EncodeStringError) -> Self {
+        WriteSaveError::Encoding(field.into(), err)
+    }
+}
+
+impl Display for WriteSaveError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            WriteSaveError::InvalidData(msg) => write!(f, "Invalid save data: {}", msg),
+            WriteSaveError::Encoding(field, err) => write!(f, "Invalid {}: {}", field, err),
+            WriteSaveError::Io(msg, err) => write!(f, "I/O Error: {} ({})", msg, err),
+        }
+    }
+}
+
+impl Error for WriteSaveError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            WriteSaveError::InvalidData(_) => None,
+            WriteSaveError::Encoding(_, err) => Some(err),
+            WriteSaveError::Io(_, err) => Some(err),
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+
+use byteorder::{ByteOrder, LittleEndian};
+
+/// Id of the first script variable, lower ids refer to special values instead of saved ones.
+pub const VARS_START: u16 = 0x4000;
+
+/// Event flags, a bitfield indexed by flag id.
+#[derive(Clone, Debug)]
+pub struct Flags(Vec<u8>);
+
+/// Script variables, indexed by var id starting at `VARS_START`.
+#[derive(Clone, Debug)]
+pub struct Vars(Vec<u16>);
+
+/// Flag and var ids by their names in the decompilation projects, e.g. `FLAG_BADGE01_GET`.
+#[derive(Clone, Debug, Default)]
+pub struct EventNames(HashMap<String, u16>);
+
+impl Flags {
+    pub fn from_bytes(data: &[u8]) -> Self {
+        Flags(data.to_vec())
+    }
+
+    pub fn write_bytes(&self, data: &mut [u8]) {
+        data.copy_from_slice(&self.0);
+    }
+
+    /// Number of flags in the save.
+    pub fn len(&self) -> usize {
+        self.0.len() * 8
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// `None` if the id is out of range.
+    pub fn get(&self, id: u16) -> Option<bool> {
+        let id = id as usize;
+        self.0.get(id / 8).map(|flags| flags & (1 << (id % 8)) != 0)
+    }
+
+    /// Returns whether the id is in range.
+    pub fn set(&mut self, id: u16, value: bool) -> bool {
+        let id = id as usize;
+        match self.0.get_mut(id / 8) {
+            Some(flags) if value => *flags |= 1 << (id % 8),
+            Some(flags) => *flags &= !(1 << (id % 8)),
+            None => return false,
+        }
+        true
+    }
+
+    pub fn get_by_name(&self, names: &EventNames, name: &str) -> Option<bool> {
+        names.get(name).and_then(|id| self.get(id))
+    }
+}
+
+impl Vars {
+    pub fn from_bytes(data: &[u8]) -> Self {
+        Vars(data.chunks(2).map(LittleEndian::read_u16).collect())
+    }
+
+    pub fn write_bytes(&self, data: &mut [u8]) {
+        LittleEndian::write_u16_into(&self.0, data);
+    }
+
+    /// Number of vars in the save.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// `None` if the id is out of range.
+    pub fn get(&self, id: u16) -> Option<u16> {
+        let index = id.checked_sub(VARS_START)?;
+        self.0.get(index as usize).copied()
+    }
+
+    /// Returns whether the id is in range.
+    pub fn set(&mut self, id: u16, value: u16) -> bool {
+        let var = id
+            .checked_sub(VARS_START)
+            .and_then(|index| self.0.get_mut(index as usize));
+        match var {
+            Some(var) => {
+                *var = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn get_by_name(&self, names: &EventNames, name: &str) -> Option<u16> {
+        names.get(name).and_then(|id| self.get(id))
+    }
+}
+
+impl EventNames {
+    /// Reads the `#define`s of a C header such as `include/constants/flags.h`. Values may be
+    /// numbers or sums of previously defined names, e.g. `(SYS_FLAGS + 0x20)`, other lines are
+    /// ignored.
+    pub fn from_defines(source: &str) -> Self {
+        let mut names = EventNames::default();
+        for line in source.lines() {
+            let line = line.split("//").next().unwrap_or_default();
+            let mut parts = line.split_whitespace();
+            if parts.next() != Some("#define") {
+                continue;
+            }
+            let name = match parts.next() {
+                Some(name) => name,
+                None => continue,
+            };
+            let expression: String = parts.collect();
+            if let Some(value) = names.evaluate(&expression) {
+                names.insert(name, value);
+            }
+        }
+        names
+    }
+
+    pub fn insert<S: Into<String>>(&mut self, name: S, id: u16) {
+        self.0.insert(name.into(), id);
+    }
+
+    pub fn get(&self, name: &str) -> Option<u16> {
+        self.0.get(name).copied()
+    }
+
+    fn evaluate(&self, expression: &str) -> Option<u16> {
+        expression
+            .trim_start_matches('(')
+            .trim_end_matches(')')
+            .split('+')
+            .try_fold(0u16, |acc, term| {
+                let value = if let Some(hex) = term.strip_prefix("0x") {
+                    u16::from_str_radix(hex, 16).ok()?
+                } else if let Ok(value) = term.parse() {
+                    value
+                } else {
+                    self.get(term)?
+                };
+                acc.checked_add(value)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FLAGS_H: &str = "#ifndef GUARD_CONSTANTS_FLAGS_H
+#define GUARD_CONSTANTS_FLAGS_H
+
+// Temporary flags
+#define FLAG_TEMP_1 0x1
+#define FLAG_HIDE_OAK 42 // Set after the intro
+#define SYS_FLAGS 0x800
+#define FLAG_BADGE01_GET (SYS_FLAGS + 0x20)
+#define FLAG_BADGE02_GET    (SYS_FLAGS + 0x21)
+#define FLAG_UNKNOWN (UNDEFINED_FLAGS + 0x1)
+
+#endif // GUARD_CONSTANTS_FLAGS_H
+";
+
+    #[test]
+    fn parse_defines() {
+        let names = EventNames::from_defines(FLAGS_H);
+        assert_eq!(names.get("FLAG_TEMP_1"), Some(0x1));
+        assert_eq!(names.get("FLAG_HIDE_OAK"), Some(42));
+        assert_eq!(names.get("SYS_FLAGS"), Some(0x800));
+        assert_eq!(names.get("FLAG_BADGE01_GET"), Some(0x820));
+        assert_eq!(names.get("FLAG_BADGE02_GET"), Some(0x821));
+        // Include guards have no value and names that were never defined can't be evaluated
+        assert_eq!(names.get("GUARD_CONSTANTS_FLAGS_H"), None);
+        assert_eq!(names.get("FLAG_UNKNOWN"), None);
+        assert_eq!(names.0.len(), 5);
+    }
+
+    #[test]
+    fn evaluate_expressions() {
+        let mut names = EventNames::default();
+        names.insert("VARS_START", VARS_START);
+        assert_eq!(names.evaluate("(VARS_START+0x10)"), Some(0x4010));
+        assert_eq!(names.evaluate("1+2+3"), Some(6));
+        assert_eq!(names.evaluate("0xFFFF+1"), None);
+        assert_eq!(names.evaluate(""), None);
+    }
+}
@@ -0,0 +1,276 @@
+use std::time::Duration;
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use poke3_common::encoding::{parse_string_lossy, STRING_TERMINATOR};
+
+use crate::text::write_string;
+use crate::version::GameVersion;
+use crate::{WriteSaveError, WriteSaveResult};
+
+const LINK_BATTLE_RECORD_SIZE: usize = 16;
+const LINK_BATTLE_RECORD_COUNT: usize = 5;
+const NAME_LENGTH: usize = 7;
+
+/// Counters the game keeps for the trainer card and other records, indexed by `GameStat`. Games
+/// with a security key XOR them with it.
+#[derive(Clone, Debug)]
+pub struct GameStats(Vec<u32>);
+
+/// Names of the game stats, shared by every game. Ruby and Sapphire stop at `EnteredHotSprings`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GameStat {
+    SavedGame,
+    /// Play time when the Hall of Fame was first entered, see `GameStats::hall_of_fame_time`
+    FirstHallOfFamePlayTime,
+    StartedTrends,
+    PlantedBerries,
+    TradedBikes,
+    Steps,
+    GotInterviewed,
+    TotalBattles,
+    WildBattles,
+    TrainerBattles,
+    EnteredHallOfFame,
+    PokemonCaptures,
+    FishingCaptures,
+    HatchedEggs,
+    EvolvedPokemon,
+    UsedPokemonCenter,
+    RestedAtHome,
+    EnteredSafariZone,
+    UsedCut,
+    UsedRockSmash,
+    MovedSecretBase,
+    PokemonTrades,
+    Unknown22,
+    LinkBattleWins,
+    LinkBattleLosses,
+    LinkBattleDraws,
+    UsedSplash,
+    UsedStruggle,
+    SlotJackpots,
+    ConsecutiveRouletteWins,
+    EnteredBattleTower,
+    Unknown31,
+    BattleTowerBestStreak,
+    Pokeblocks,
+    PokeblocksWithFriends,
+    WonLinkContest,
+    EnteredContest,
+    WonContest,
+    Shopped,
+    UsedItemfinder,
+    GotRainedOn,
+    CheckedPokedex,
+    ReceivedRibbons,
+    JumpedDownLedges,
+    WatchedTv,
+    CheckedClock,
+    WonPokemonLottery,
+    UsedDayCare,
+    RodeCableCar,
+    EnteredHotSprings,
+    UnionRoomBattles,
+    PlayedBerryCrush,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BattleRecord {
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+}
+
+/// Trainer card stars shared by every game, as awarded in the decompilations'
+/// `src/trainer_card.c`. The others depend on data that isn't decoded: contest paintings and the
+/// Battle Tower or Frontier in Ruby/Sapphire/Emerald, link minigames in FireRed/LeafGreen.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TrainerCardStars {
+    /// Entered the Hall of Fame, judged from its recorded play time like the game does
+    pub hall_of_fame: bool,
+    /// Caught every species of the game's Pokédex, see `TrainerCardStars::pokedex_species`
+    pub pokedex_complete: bool,
+}
+
+/// Record against one of the last link battle opponents, shown in the Battle Tower and on the
+/// trainer card.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LinkBattleOpponent {
+    pub name: String,
+    /// Public part of the opponent's trainer id
+    pub trainer_id: u16,
+    pub record: BattleRecord,
+}
+
+impl GameStats {
+    pub fn from_bytes(data: &[u8], key: u32) -> Self {
+        GameStats(
+            data.chunks(4)
+                .map(|raw| LittleEndian::read_u32(raw) ^ key)
+                .collect(),
+        )
+    }
+
+    pub fn write_bytes(&self, data: &mut [u8], key: u32) {
+        for (raw, value) in data.chunks_mut(4).zip(&self.0) {
+            LittleEndian::write_u32(raw, value ^ key);
+        }
+    }
+
+    /// Number of stats in the save, including unused ones.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// `None` if the game doesn't have this stat.
+    pub fn get(&self, stat: GameStat) -> Option<u32> {
+        self.get_raw(stat as usize)
+    }
+
+    /// Returns whether the game has this stat.
+    pub fn set(&mut self, stat: GameStat, value: u32) -> bool {
+        self.set_raw(stat as usize, value)
+    }
+
+    /// Stat by index, for the unused ones past `GameStat::PlayedBerryCrush`.
+    pub fn get_raw(&self, index: usize) -> Option<u32> {
+        self.0.get(index).copied()
+    }
+
+    pub fn set_raw(&mut self, index: usize, value: u32) -> bool {
+        match self.0.get_mut(index) {
+            Some(stat) => {
+                *stat = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Play time when the player first entered the Hall of Fame, `None` if they haven't yet.
+    pub fn hall_of_fame_time(&self) -> Option<Duration> {
+        let raw = self.get(GameStat::FirstHallOfFamePlayTime)?;
+        if raw == 0 {
+            return None;
+        }
+        let hours = (raw >> 16) as u64;
+        let minutes = (raw >> 8 & 0xFF) as u64;
+        let seconds = (raw & 0xFF) as u64;
+        Some(Duration::from_secs(
+            hours * 60 * 60 + minutes * 60 + seconds,
+        ))
+    }
+
+    /// Wins, losses and draws over every link battle.
+    pub fn link_battle_record(&self) -> BattleRecord {
+        let get = |stat| self.get(stat).unwrap_or(0);
+        BattleRecord {
+            wins: get(GameStat::LinkBattleWins),
+            losses: get(GameStat::LinkBattleLosses),
+            draws: get(GameStat::LinkBattleDraws),
+        }
+    }
+}
+
+impl TrainerCardStars {
+    /// National dex ranges FireRed/LeafGreen's `HasAllMons` requires, which leave out the
+    /// event-only Mew, Lugia, Ho-Oh, Celebi, Jirachi and Deoxys.
+    pub const NATIONAL_DEX_RANGES: [(u16, u16); 3] = [(1, 150), (152, 248), (252, 384)];
+
+    /// Species of the Hoenn dex from before Generation III, by national dex number. Along with
+    /// Treecko to Rayquaza they make the 200 entries Ruby/Sapphire/Emerald's `HasAllHoennMons`
+    /// requires, leaving out Jirachi and Deoxys.
+    pub const HOENN_DEX_OLDER_SPECIES: [u16; 67] = [
+        25, 26, 27, 28, 37, 38, 39, 40, 41, 42, 43, 44, 45, 54, 55, 63, 64, 65, 66, 67, 68, 72, 73,
+        74, 75, 76, 81, 82, 84, 85, 88, 89, 100, 101, 109, 110, 111, 112, 116, 117, 118, 119, 120,
+        121, 127, 129, 130, 169, 170, 171, 172, 174, 177, 178, 182, 183, 184, 202, 203, 214, 218,
+        219, 222, 227, 230, 231, 232,
+    ];
+    const HOENN_DEX_GEN_3: (u16, u16) = (252, 384);
+
+    /// National dex numbers that must be caught for the Pokédex star.
+    pub fn pokedex_species(version: GameVersion) -> Vec<u16> {
+        match version {
+            GameVersion::FireRedLeafGreen | GameVersion::Cfru => Self::NATIONAL_DEX_RANGES
+                .iter()
+                .flat_map(|&(first, last)| first..=last)
+                .collect(),
+            GameVersion::RubySapphire | GameVersion::Emerald => {
+                let (first, last) = Self::HOENN_DEX_GEN_3;
+                Self::HOENN_DEX_OLDER_SPECIES
+                    .iter()
+                    .copied()
+                    .chain(first..=last)
+                    .collect()
+            }
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.hall_of_fame as usize + self.pokedex_complete as usize
+    }
+}
+
+/// Reads the non-empty entries of the link battle records.
+pub fn read_link_battle_records(data: &[u8]) -> Vec<LinkBattleOpponent> {
+    data[..LINK_BATTLE_RECORD_COUNT * LINK_BATTLE_RECORD_SIZE]
+        .chunks(LINK_BATTLE_RECORD_SIZE)
+        .map(|raw| LinkBattleOpponent {
+            name: parse_string_lossy(&raw[..NAME_LENGTH + 1]),
+            trainer_id: LittleEndian::read_u16(&raw[8..]),
+            record: BattleRecord {
+                wins: LittleEndian::read_u16(&raw[10..]) as u32,
+                losses: LittleEndian::read_u16(&raw[12..]) as u32,
+                draws: LittleEndian::read_u16(&raw[14..]) as u32,
+            },
+        })
+        // Saves that never cleared the records have zeroed entries instead of empty names
+        .filter(|opponent| !opponent.name.is_empty() && opponent.record != BattleRecord::default())
+        .collect()
+}
+
+/// Writes the link battle records, clearing the remaining entries. Counts are capped at 9999 like
+/// in the game.
+pub fn write_link_battle_records(
+    data: &mut [u8],
+    records: &[LinkBattleOpponent],
+) -> WriteSaveResult<()> {
+    if records.len() > LINK_BATTLE_RECORD_COUNT {
+        return Err(WriteSaveError::InvalidData(format!(
+            "Link battle records has {} entries, expected at most {}",
+            records.len(),
+            LINK_BATTLE_RECORD_COUNT
+        )));
+    }
+    let entries = data[..LINK_BATTLE_RECORD_COUNT * LINK_BATTLE_RECORD_SIZE]
+        .chunks_mut(LINK_BATTLE_RECORD_SIZE);
+    for (i, raw) in entries.enumerate() {
+        let record = match records.get(i) {
+            Some(record) => record,
+            None => {
+                raw.iter_mut().for_each(|b| *b = 0);
+                raw[0] = STRING_TERMINATOR;
+                continue;
+            }
+        };
+        if parse_string_lossy(&raw[..NAME_LENGTH + 1]) != record.name {
+            write_string(
+                &mut raw[..NAME_LENGTH],
+                &record.name,
+                "link battle opponent name",
+            )?;
+            raw[NAME_LENGTH] = STRING_TERMINATOR;
+        }
+        let cap = |count: u32| count.min(9999) as u16;
+        LittleEndian::write_u16(&mut raw[8..], record.trainer_id);
+        LittleEndian::write_u16(&mut raw[10..], cap(record.record.wins));
+        LittleEndian::write_u16(&mut raw[12..], cap(record.record.losses));
+        LittleEndian::write_u16(&mut raw[14..], cap(record.record.draws));
+    }
+    Ok(())
+}
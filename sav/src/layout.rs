@@ -0,0 +1,211 @@
+use poke3_common::rom::ItemPocket;
+
+use crate::pokemon::PokemonFormat;
+
+/// Sizes and offsets of the save structures, which differ between games.
+#[derive(Debug)]
+pub struct Layout {
+    pub save_block_2_size: usize,
+    pub save_block_1_size: usize,
+    /// Whether sector checksums cover the whole sector instead of just the chunk of the save
+    /// structures they hold
+    pub full_sector_checksums: bool,
+    /// Offset in SaveBlock2 of the key money, coins and bag quantities are XORed with, if the game
+    /// encrypts them
+    pub security_key: Option<usize>,
+    pub pokemon_format: PokemonFormat,
+    /// Offset of the party size in SaveBlock1, the party itself follows it
+    pub party: usize,
+    pub money: usize,
+    pub coins: usize,
+    pub pc_items: PocketLayout,
+    /// `None` if the bag isn't stored in the vanilla structures
+    pub bag: Option<BagLayout>,
+    pub pokedex: PokedexLayout,
+    pub national_dex: NationalDexLayout,
+    /// `None` if the Day Care isn't supported
+    pub daycare: Option<usize>,
+    /// `None` if the roamer isn't supported
+    pub roamer: Option<usize>,
+    pub flags: usize,
+    /// Size in bytes of the flags bitfield
+    pub flags_size: usize,
+    pub vars: usize,
+    pub vars_count: usize,
+    pub game_stats: usize,
+    pub game_stats_count: usize,
+    /// `None` if the last link battle opponents aren't supported
+    pub link_battle_records: Option<usize>,
+}
+
+/// The games only consider the national dex unlocked if a magic byte in SaveBlock2, a var and a
+/// flag all hold the expected values.
+#[derive(Debug)]
+pub struct NationalDexLayout {
+    pub magic: u8,
+    pub var: u16,
+    pub var_value: u16,
+    pub flag: u16,
+}
+
+/// Location in SaveBlock1 of a list of item slots.
+#[derive(Debug)]
+pub struct PocketLayout {
+    pub offset: usize,
+    pub capacity: usize,
+}
+
+#[derive(Debug)]
+pub struct BagLayout {
+    pub items: PocketLayout,
+    pub key_items: PocketLayout,
+    pub poke_balls: PocketLayout,
+    pub tm_case: PocketLayout,
+    pub berry_pouch: PocketLayout,
+}
+
+impl BagLayout {
+    pub fn pocket(&self, pocket: ItemPocket) -> &PocketLayout {
+        match pocket {
+            ItemPocket::Items => &self.items,
+            ItemPocket::KeyItems => &self.key_items,
+            ItemPocket::PokeBalls => &self.poke_balls,
+            ItemPocket::TmCase => &self.tm_case,
+            ItemPocket::BerryPouch => &self.berry_pouch,
+        }
+    }
+}
+
+const fn pocket(offset: usize, capacity: usize) -> PocketLayout {
+    PocketLayout { offset, capacity }
+}
+
+#[derive(Debug)]
+pub enum PokedexLayout {
+    /// Owned and seen flags for the 386 national dex entries, kept in SaveBlock2. The seen flags
+    /// are mirrored at the given SaveBlock1 offsets, the game only counts species seen in all three.
+    Vanilla { seen_mirrors: [usize; 2] },
+    /// Expanded flags, relocated to SaveBlock1
+    Cfru { seen: usize, caught: usize },
+}
+
+pub const RUBY_SAPPHIRE: Layout = Layout {
+    save_block_2_size: 0x0890,
+    save_block_1_size: 0x3AC0,
+    full_sector_checksums: false,
+    security_key: None,
+    pokemon_format: PokemonFormat::Vanilla,
+    party: 0x0234,
+    money: 0x0490,
+    coins: 0x0494,
+    pc_items: pocket(0x0498, 50),
+    bag: Some(BagLayout {
+        items: pocket(0x0560, 20),
+        key_items: pocket(0x05B0, 20),
+        poke_balls: pocket(0x0600, 16),
+        tm_case: pocket(0x0640, 64),
+        berry_pouch: pocket(0x0740, 46),
+    }),
+    pokedex: PokedexLayout::Vanilla {
+        seen_mirrors: [0x0938, 0x3A8C],
+    },
+    national_dex: NationalDexLayout {
+        magic: 0xDA,
+        var: 0x4046,
+        var_value: 0x0302,
+        flag: 0x0836,
+    },
+    // Ruby/Sapphire store the Day Care Pokémon and their data in separate arrays
+    daycare: None,
+    roamer: Some(0x3144),
+    flags: 0x1220,
+    flags_size: 0x0120,
+    vars: 0x1340,
+    vars_count: 0x0100,
+    game_stats: 0x1540,
+    game_stats_count: 50,
+    link_battle_records: None,
+};
+
+pub const EMERALD: Layout = Layout {
+    save_block_2_size: 0x0F2C,
+    save_block_1_size: 0x3D88,
+    security_key: Some(0x00AC),
+    bag: Some(BagLayout {
+        items: pocket(0x0560, 30),
+        key_items: pocket(0x05D8, 30),
+        poke_balls: pocket(0x0650, 16),
+        tm_case: pocket(0x0690, 64),
+        berry_pouch: pocket(0x0790, 46),
+    }),
+    pokedex: PokedexLayout::Vanilla {
+        seen_mirrors: [0x0988, 0x3B24],
+    },
+    national_dex: NationalDexLayout {
+        flag: 0x0896,
+        ..RUBY_SAPPHIRE.national_dex
+    },
+    daycare: Some(0x3030),
+    roamer: Some(0x31DC),
+    flags: 0x1270,
+    flags_size: 0x012C,
+    vars: 0x139C,
+    game_stats: 0x159C,
+    game_stats_count: 64,
+    link_battle_records: Some(0x3150),
+    ..RUBY_SAPPHIRE
+};
+
+pub const FIRE_RED_LEAF_GREEN: Layout = Layout {
+    save_block_2_size: 0x0F24,
+    save_block_1_size: 0x3D68,
+    full_sector_checksums: false,
+    security_key: Some(0x0F20),
+    pokemon_format: PokemonFormat::Vanilla,
+    party: 0x0034,
+    money: 0x0290,
+    coins: 0x0294,
+    pc_items: pocket(0x0298, 30),
+    bag: Some(BagLayout {
+        items: pocket(0x0310, 42),
+        key_items: pocket(0x03B8, 30),
+        poke_balls: pocket(0x0430, 13),
+        tm_case: pocket(0x0464, 58),
+        berry_pouch: pocket(0x054C, 43),
+    }),
+    pokedex: PokedexLayout::Vanilla {
+        seen_mirrors: [0x05F8, 0x3A18],
+    },
+    national_dex: NationalDexLayout {
+        magic: 0xB9,
+        var: 0x404E,
+        var_value: 0x6258,
+        flag: 0x0840,
+    },
+    daycare: Some(0x2F80),
+    roamer: Some(0x30D0),
+    flags: 0x0EE0,
+    flags_size: 0x0120,
+    vars: 0x1000,
+    vars_count: 0x0100,
+    game_stats: 0x1200,
+    game_stats_count: 64,
+    link_battle_records: None,
+};
+
+pub const CFRU: Layout = Layout {
+    full_sector_checksums: true,
+    // CFRU stores money and items unencrypted
+    security_key: None,
+    pokemon_format: PokemonFormat::Cfru,
+    // The expanded bag is moved out of the vanilla pockets, whose space is reused by the pokedex.
+    // Where it's relocated to isn't decoded yet, so CFRU saves have no bag.
+    bag: None,
+    // CFRU supports several roamers, kept outside of the vanilla structure
+    roamer: None,
+    pokedex: PokedexLayout::Cfru {
+        seen: 0x0310,
+        caught: 0x038D,
+    },
+    ..FIRE_RED_LEAF_GREEN
+};
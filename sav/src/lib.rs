@@ -0,0 +1,24 @@
+pub mod bag;
+pub mod blocks;
+pub mod daycare;
+mod error;
+pub mod events;
+pub mod game_stats;
+pub mod layout;
+pub mod location;
+pub mod pokedex;
+pub mod pokemon;
+pub mod progress;
+pub mod roamer;
+pub mod section;
+pub mod sector;
+pub mod storage;
+mod text;
+pub mod trainer;
+pub mod version;
+
+pub use blocks::SaveBlocks;
+pub use error::{LoadSaveError, LoadSaveResult, WriteSaveError, WriteSaveResult};
+pub use section::{LoadOptions, Save};
+pub use trainer::TrainerId;
+pub use version::GameVersion;
@@ -0,0 +1,109 @@
+use std::fmt::{self, Display, Formatter};
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use poke3_common::rom::{Map, Rom};
+
+const WARP_SIZE: usize = 8;
+const CURRENT_MAP_OFFSET: usize = 0x04;
+const CONTINUE_GAME_WARP_OFFSET: usize = 0x0C;
+const DYNAMIC_WARP_OFFSET: usize = 0x14;
+const RESPAWN_OFFSET: usize = 0x1C;
+const ESCAPE_WARP_OFFSET: usize = 0x24;
+
+/// Where the player is, and the warps the game keeps to send them elsewhere. Stored at the start
+/// of SaveBlock1 in every game.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Location {
+    pub x: i16,
+    pub y: i16,
+    pub current_map: Warp,
+    /// Where the player appears when continuing the game
+    pub continue_game_warp: Warp,
+    /// Destination of warps that lead back to where the player came from, e.g. exits of buildings
+    /// entered from several maps
+    pub last_warp: Warp,
+    /// Pokémon Center or home the player is sent to after whiting out
+    pub respawn: Warp,
+    /// Where Escape Rope and Dig lead
+    pub escape_warp: Warp,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Warp {
+    pub map: MapId,
+    /// Index of the warp in the map, -1 to use the coordinates instead
+    pub warp_id: i8,
+    pub x: i16,
+    pub y: i16,
+}
+
+/// Map group and number, matching the `MAP_*` constants of the decompilation projects.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MapId {
+    pub group: u8,
+    pub number: u8,
+}
+
+impl Location {
+    pub fn from_bytes(data: &[u8]) -> Self {
+        Location {
+            x: LittleEndian::read_i16(data),
+            y: LittleEndian::read_i16(&data[2..]),
+            current_map: Warp::from_bytes(&data[CURRENT_MAP_OFFSET..]),
+            continue_game_warp: Warp::from_bytes(&data[CONTINUE_GAME_WARP_OFFSET..]),
+            last_warp: Warp::from_bytes(&data[DYNAMIC_WARP_OFFSET..]),
+            respawn: Warp::from_bytes(&data[RESPAWN_OFFSET..]),
+            escape_warp: Warp::from_bytes(&data[ESCAPE_WARP_OFFSET..]),
+        }
+    }
+
+    pub fn write_bytes(&self, data: &mut [u8]) {
+        LittleEndian::write_i16(data, self.x);
+        LittleEndian::write_i16(&mut data[2..], self.y);
+        self.current_map
+            .write_bytes(&mut data[CURRENT_MAP_OFFSET..]);
+        self.continue_game_warp
+            .write_bytes(&mut data[CONTINUE_GAME_WARP_OFFSET..]);
+        self.last_warp.write_bytes(&mut data[DYNAMIC_WARP_OFFSET..]);
+        self.respawn.write_bytes(&mut data[RESPAWN_OFFSET..]);
+        self.escape_warp
+            .write_bytes(&mut data[ESCAPE_WARP_OFFSET..]);
+    }
+}
+
+impl Warp {
+    pub fn from_bytes(data: &[u8]) -> Self {
+        Warp {
+            map: MapId {
+                group: data[0],
+                number: data[1],
+            },
+            warp_id: data[2] as i8,
+            x: LittleEndian::read_i16(&data[4..]),
+            y: LittleEndian::read_i16(&data[6..]),
+        }
+    }
+
+    pub fn write_bytes(&self, data: &mut [u8]) {
+        let data = &mut data[..WARP_SIZE];
+        data[0] = self.map.group;
+        data[1] = self.map.number;
+        data[2] = self.warp_id as u8;
+        LittleEndian::write_i16(&mut data[4..], self.x);
+        LittleEndian::write_i16(&mut data[6..], self.y);
+    }
+}
+
+impl MapId {
+    /// Map data for this id, `None` if the ROM has no map with this id.
+    pub fn resolve(self, rom: &Rom) -> Option<&Map> {
+        rom.map(self.group, self.number)
+    }
+}
+
+impl Display for MapId {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}.{}", self.group, self.number)
+    }
+}
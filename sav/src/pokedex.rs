@@ -0,0 +1,214 @@
+use std::fmt::{self, Display, Formatter};
+
+use poke3_common::rom::{Rom, Species};
+
+use crate::blocks::SaveBlocks;
+use crate::events::{Flags, Vars};
+use crate::layout::{Layout, PokedexLayout};
+
+/// Entries in the vanilla national dex
+const VANILLA_POKEDEX_COUNT: usize = 386;
+/// Size of each of the vanilla owned and seen flag arrays in SaveBlock2
+const VANILLA_POKEDEX_FLAGS_SIZE: usize = 52;
+const VANILLA_POKEDEX_OWNED: usize = 0x0028;
+const VANILLA_POKEDEX_SEEN: usize = 0x005C;
+/// Size of each of the expanded CFRU flag arrays in SaveBlock1
+const CFRU_POKEDEX_FLAGS_SIZE: usize = 125;
+/// Offset in SaveBlock2 of the magic byte that's part of unlocking the national dex
+const NATIONAL_MAGIC_OFFSET: usize = 0x001A;
+
+/// Seen and caught flags of every Pokédex entry.
+#[derive(Clone, Debug)]
+pub struct Pokedex {
+    entries: Vec<PokedexEntry>,
+    /// Whether the national dex is unlocked, see `Save::set_national_dex`
+    pub national_dex: bool,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PokedexEntry {
+    pub seen: bool,
+    pub caught: bool,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SpeciesId(pub u16);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NationalDexId(pub u16);
+
+impl Pokedex {
+    pub fn read(blocks: &SaveBlocks, flags: &Flags, vars: &Vars) -> Self {
+        let layout = blocks.version.layout();
+        let (data, seen, caught, size, count) = flag_arrays(layout);
+        let data = match data {
+            Block::SaveBlock2 => &blocks.save_block_2,
+            Block::SaveBlock1 => &blocks.save_block_1,
+        };
+        let mut entries: Vec<_> = (0..count)
+            .map(|i| PokedexEntry {
+                seen: get_bit(&data[seen..seen + size], i),
+                caught: get_bit(&data[caught..caught + size], i),
+            })
+            .collect();
+        // The game only counts a species as seen if it's set in every copy
+        if let PokedexLayout::Vanilla { seen_mirrors } = layout.pokedex {
+            for mirror in &seen_mirrors {
+                let mirror = &blocks.save_block_1[*mirror..*mirror + size];
+                for (i, entry) in entries.iter_mut().enumerate() {
+                    entry.seen &= get_bit(mirror, i);
+                }
+            }
+        }
+
+        let national = &layout.national_dex;
+        let national_dex = blocks.save_block_2[NATIONAL_MAGIC_OFFSET] == national.magic
+            && vars.get(national.var) == Some(national.var_value)
+            && flags.get(national.flag) == Some(true);
+
+        Pokedex {
+            entries,
+            national_dex,
+        }
+    }
+
+    /// Writes the flags, and the magic byte if the national dex is unlocked. The flag and var
+    /// unlocking it are kept in `Save::flags` and `Save::vars`.
+    pub fn write(&self, blocks: &mut SaveBlocks) {
+        let layout = blocks.version.layout();
+        let (data, seen, caught, size, _) = flag_arrays(layout);
+        let data = match data {
+            Block::SaveBlock2 => &mut blocks.save_block_2,
+            Block::SaveBlock1 => &mut blocks.save_block_1,
+        };
+        set_bits(
+            &mut data[seen..seen + size],
+            self.entries.iter().map(|e| e.seen),
+        );
+        set_bits(
+            &mut data[caught..caught + size],
+            self.entries.iter().map(|e| e.caught),
+        );
+        if let PokedexLayout::Vanilla { seen_mirrors } = layout.pokedex {
+            for mirror in &seen_mirrors {
+                let mirror = &mut blocks.save_block_1[*mirror..*mirror + size];
+                set_bits(mirror, self.entries.iter().map(|e| e.seen));
+            }
+        }
+
+        let magic = &mut blocks.save_block_2[NATIONAL_MAGIC_OFFSET];
+        if self.national_dex {
+            *magic = layout.national_dex.magic;
+        } else if *magic == layout.national_dex.magic {
+            *magic = 0;
+        }
+    }
+
+    /// Number of entries, 386 in the vanilla games.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// `None` if the id is out of range.
+    pub fn get(&self, id: NationalDexId) -> Option<PokedexEntry> {
+        let index = (id.0 as usize).checked_sub(1)?;
+        self.entries.get(index).copied()
+    }
+
+    pub fn get_mut(&mut self, id: NationalDexId) -> Option<&mut PokedexEntry> {
+        let index = (id.0 as usize).checked_sub(1)?;
+        self.entries.get_mut(index)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (NationalDexId, PokedexEntry)> + '_ {
+        self.entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| (NationalDexId(i as u16 + 1), *entry))
+    }
+
+    pub fn seen_count(&self) -> usize {
+        self.entries.iter().filter(|e| e.seen).count()
+    }
+
+    pub fn caught_count(&self) -> usize {
+        self.entries.iter().filter(|e| e.caught).count()
+    }
+}
+
+enum Block {
+    SaveBlock2,
+    SaveBlock1,
+}
+
+/// Block, seen offset, caught offset, size in bytes and number of entries of the flag arrays.
+fn flag_arrays(layout: &Layout) -> (Block, usize, usize, usize, usize) {
+    match layout.pokedex {
+        PokedexLayout::Vanilla { .. } => (
+            Block::SaveBlock2,
+            VANILLA_POKEDEX_SEEN,
+            VANILLA_POKEDEX_OWNED,
+            VANILLA_POKEDEX_FLAGS_SIZE,
+            VANILLA_POKEDEX_COUNT,
+        ),
+        PokedexLayout::Cfru { seen, caught } => (
+            Block::SaveBlock1,
+            seen,
+            caught,
+            CFRU_POKEDEX_FLAGS_SIZE,
+            CFRU_POKEDEX_FLAGS_SIZE * 8,
+        ),
+    }
+}
+
+fn get_bit(flags: &[u8], index: usize) -> bool {
+    flags[index / 8] & (1 << (index % 8)) != 0
+}
+
+/// Sets the first bits to the given values, leaving the rest alone.
+fn set_bits<I: Iterator<Item = bool>>(flags: &mut [u8], values: I) {
+    for (index, value) in values.enumerate() {
+        let mask = 1 << (index % 8);
+        if value {
+            flags[index / 8] |= mask;
+        } else {
+            flags[index / 8] &= !mask;
+        }
+    }
+}
+
+impl Display for SpeciesId {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl SpeciesId {
+    /// Species data for this id, `None` if the ROM has no species with this id. The ROM's species
+    /// start at id 1, there's no data for the NONE placeholder.
+    pub fn resolve(self, rom: &Rom) -> Option<&Species> {
+        rom.species.get(self.0.checked_sub(1)? as usize)
+    }
+}
+
+impl From<u16> for SpeciesId {
+    fn from(id: u16) -> Self {
+        SpeciesId(id)
+    }
+}
+
+impl Display for NationalDexId {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl From<u16> for NationalDexId {
+    fn from(id: u16) -> Self {
+        NationalDexId(id)
+    }
+}
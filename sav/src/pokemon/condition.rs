@@ -0,0 +1,96 @@
+/// Contest conditions, raised with Pokéblocks or Berries.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ContestStats {
+    pub cool: u8,
+    pub beauty: u8,
+    pub cute: u8,
+    pub smart: u8,
+    pub tough: u8,
+    pub sheen: u8,
+}
+
+/// Pokérus infection, a strain of 0 means the Pokémon was never infected.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Pokerus {
+    pub strain: u8,
+    /// Days until the infection is cured, 0 once it is
+    pub days: u8,
+}
+
+/// Non-volatile status condition of a party Pokémon.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatusCondition {
+    Healthy,
+    Sleep {
+        turns: u8,
+    },
+    Poison,
+    Burn,
+    Freeze,
+    Paralysis,
+    /// Badly poisoned
+    Toxic,
+}
+
+impl Default for StatusCondition {
+    fn default() -> Self {
+        StatusCondition::Healthy
+    }
+}
+
+impl Pokerus {
+    pub fn from_raw(raw: u8) -> Self {
+        Pokerus {
+            strain: raw >> 4,
+            days: raw & 0x0F,
+        }
+    }
+
+    pub fn to_raw(self) -> u8 {
+        (self.strain & 0x0F) << 4 | (self.days & 0x0F)
+    }
+
+    pub fn is_infected(self) -> bool {
+        self.strain != 0 && self.days != 0
+    }
+
+    /// Cured Pokémon can't be infected again, but keep the doubled EV gains.
+    pub fn is_cured(self) -> bool {
+        self.strain != 0 && self.days == 0
+    }
+}
+
+impl StatusCondition {
+    /// Decodes the lowest byte of the status word, in the order the game checks it.
+    pub fn from_raw(raw: u32) -> Self {
+        if raw & 0b111 != 0 {
+            StatusCondition::Sleep {
+                turns: (raw & 0b111) as u8,
+            }
+        } else if raw & (1 << 3) != 0 {
+            StatusCondition::Poison
+        } else if raw & (1 << 4) != 0 {
+            StatusCondition::Burn
+        } else if raw & (1 << 5) != 0 {
+            StatusCondition::Freeze
+        } else if raw & (1 << 6) != 0 {
+            StatusCondition::Paralysis
+        } else if raw & (1 << 7) != 0 {
+            StatusCondition::Toxic
+        } else {
+            StatusCondition::Healthy
+        }
+    }
+
+    pub fn to_raw(self) -> u32 {
+        match self {
+            StatusCondition::Healthy => 0,
+            StatusCondition::Sleep { turns } => turns as u32 & 0b111,
+            StatusCondition::Poison => 1 << 3,
+            StatusCondition::Burn => 1 << 4,
+            StatusCondition::Freeze => 1 << 5,
+            StatusCondition::Paralysis => 1 << 6,
+            StatusCondition::Toxic => 1 << 7,
+        }
+    }
+}
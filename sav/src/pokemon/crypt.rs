@@ -0,0 +1,141 @@
+use byteorder::{ByteOrder, LittleEndian};
+
+const SUBSTRUCTURES_OFFSET: usize = 32;
+const SUBSTRUCTURE_SIZE: usize = 12;
+const SUBSTRUCTURES_SIZE: usize = 4 * SUBSTRUCTURE_SIZE;
+
+/// Which substructure (growth, attacks, EVs, misc) is stored at each position, indexed by
+/// personality % 24.
+const SUBSTRUCTURE_ORDERS: [[usize; 4]; 24] = [
+    [0, 1, 2, 3], // GAEM
+    [0, 1, 3, 2], // GAME
+    [0, 2, 1, 3], // GEAM
+    [0, 2, 3, 1], // GEMA
+    [0, 3, 1, 2], // GMAE
+    [0, 3, 2, 1], // GMEA
+    [1, 0, 2, 3], // AGEM
+    [1, 0, 3, 2], // AGME
+    [1, 2, 0, 3], // AEGM
+    [1, 2, 3, 0], // AEMG
+    [1, 3, 0, 2], // AMGE
+    [1, 3, 2, 0], // AMEG
+    [2, 0, 1, 3], // EGAM
+    [2, 0, 3, 1], // EGMA
+    [2, 1, 0, 3], // EAGM
+    [2, 1, 3, 0], // EAMG
+    [2, 3, 0, 1], // EMGA
+    [2, 3, 1, 0], // EMAG
+    [3, 0, 1, 2], // MGAE
+    [3, 0, 2, 1], // MGEA
+    [3, 1, 0, 2], // MAGE
+    [3, 1, 2, 0], // MAEG
+    [3, 2, 0, 1], // MEGA
+    [3, 2, 1, 0], // MEAG
+];
+
+/// Decrypts the substructures of vanilla box data in place, leaving them in GAEM order.
+pub fn decrypt(data: &mut [u8]) {
+    xor_substructures(data);
+    let order = substructure_order(data);
+    let mut decoded = [0u8; SUBSTRUCTURES_SIZE];
+    for (position, substructure) in order.iter().enumerate() {
+        decoded[substructure_range(*substructure)]
+            .copy_from_slice(&data[SUBSTRUCTURES_OFFSET..][substructure_range(position)]);
+    }
+    data[SUBSTRUCTURES_OFFSET..SUBSTRUCTURES_OFFSET + SUBSTRUCTURES_SIZE].copy_from_slice(&decoded);
+}
+
+/// Inverse of `decrypt`, shuffles GAEM ordered substructures and encrypts them in place.
+pub fn encrypt(data: &mut [u8]) {
+    let order = substructure_order(data);
+    let mut encoded = [0u8; SUBSTRUCTURES_SIZE];
+    for (position, substructure) in order.iter().enumerate() {
+        encoded[substructure_range(position)]
+            .copy_from_slice(&data[SUBSTRUCTURES_OFFSET..][substructure_range(*substructure)]);
+    }
+    data[SUBSTRUCTURES_OFFSET..SUBSTRUCTURES_OFFSET + SUBSTRUCTURES_SIZE].copy_from_slice(&encoded);
+    xor_substructures(data);
+}
+
+fn substructure_order(data: &[u8]) -> &'static [usize; 4] {
+    let personality = LittleEndian::read_u32(data);
+    &SUBSTRUCTURE_ORDERS[(personality % 24) as usize]
+}
+
+fn substructure_range(index: usize) -> std::ops::Range<usize> {
+    index * SUBSTRUCTURE_SIZE..(index + 1) * SUBSTRUCTURE_SIZE
+}
+
+/// Substructures are encrypted by XORing each word with the personality value and OT id.
+fn xor_substructures(data: &mut [u8]) {
+    let key = LittleEndian::read_u32(data) ^ LittleEndian::read_u32(&data[4..]);
+    for word in data[SUBSTRUCTURES_OFFSET..SUBSTRUCTURES_OFFSET + SUBSTRUCTURES_SIZE].chunks_mut(4)
+    {
+        let value = LittleEndian::read_u32(word);
+        LittleEndian::write_u32(word, value ^ key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Box data with the given header and each substructure filled with a repeated word.
+    fn box_data(personality: u32, ot_id: u32, words: [u32; 4]) -> Vec<u8> {
+        let mut data = vec![0u8; SUBSTRUCTURES_OFFSET + SUBSTRUCTURES_SIZE];
+        LittleEndian::write_u32(&mut data, personality);
+        LittleEndian::write_u32(&mut data[4..], ot_id);
+        for (i, word) in words.iter().enumerate() {
+            for raw in data[SUBSTRUCTURES_OFFSET..][substructure_range(i)].chunks_mut(4) {
+                LittleEndian::write_u32(raw, *word);
+            }
+        }
+        data
+    }
+
+    #[test]
+    fn decrypt_known_pokemon() {
+        // Personality 25 is stored in GAME order, the key is 25 ^ 0x100
+        let mut data = box_data(
+            25,
+            0x100,
+            [0x1111_1008, 0x2222_233B, 0x4444_455D, 0x3333_322A],
+        );
+        decrypt(&mut data);
+        assert_eq!(
+            data,
+            box_data(
+                25,
+                0x100,
+                [0x1111_1111, 0x2222_2222, 0x3333_3333, 0x4444_4444]
+            )
+        );
+    }
+
+    #[test]
+    fn encrypt_round_trip() {
+        for personality in 0..24 {
+            let personality = 0xABCD_0000 + personality * 0x1_0001;
+            let decrypted = box_data(personality, 0x1234_5678, [1, 2, 3, 4]);
+            let mut encrypted = decrypted.clone();
+            encrypt(&mut encrypted);
+            assert_ne!(encrypted, decrypted);
+
+            let mut data = encrypted.clone();
+            decrypt(&mut data);
+            assert_eq!(data, decrypted, "personality {:#x}", personality);
+            encrypt(&mut data);
+            assert_eq!(data, encrypted, "personality {:#x}", personality);
+        }
+    }
+
+    #[test]
+    fn orders_are_distinct_permutations() {
+        for (i, order) in SUBSTRUCTURE_ORDERS.iter().enumerate() {
+            let mut sorted = *order;
+            sorted.sort_unstable();
+            assert_eq!(sorted, [0, 1, 2, 3]);
+            assert!(!SUBSTRUCTURE_ORDERS[..i].contains(order));
+        }
+    }
+}
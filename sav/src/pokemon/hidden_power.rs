@@ -0,0 +1,56 @@
+use poke3_common::rom::{PokemonType, Stats};
+
+/// Types Hidden Power can have, indexed by the value derived from the IVs.
+const HIDDEN_POWER_TYPES: [PokemonType; 16] = [
+    PokemonType::Fighting,
+    PokemonType::Flying,
+    PokemonType::Poison,
+    PokemonType::Ground,
+    PokemonType::Rock,
+    PokemonType::Bug,
+    PokemonType::Ghost,
+    PokemonType::Steel,
+    PokemonType::Fire,
+    PokemonType::Water,
+    PokemonType::Grass,
+    PokemonType::Electric,
+    PokemonType::Psychic,
+    PokemonType::Ice,
+    PokemonType::Dragon,
+    PokemonType::Dark,
+];
+
+const RADICAL_RED_POWER: u8 = 60;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HiddenPower {
+    pub move_type: PokemonType,
+    pub power: u8,
+}
+
+/// How Hidden Power's power is calculated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HiddenPowerVariant {
+    /// Between 30 and 70, derived from the IVs
+    Vanilla,
+    /// Always 60, like in later generations
+    RadicalRed,
+}
+
+impl HiddenPower {
+    pub fn from_ivs(ivs: &Stats<u8>, variant: HiddenPowerVariant) -> Self {
+        // Each IV contributes one bit, in HP, Atk, Def, Spe, SpA, SpD order
+        let bits = |bit: u8| {
+            [ivs.hp, ivs.atk, ivs.def, ivs.spe, ivs.spa, ivs.spd]
+                .iter()
+                .enumerate()
+                .fold(0u32, |acc, (i, iv)| acc | (((iv >> bit) & 1) as u32) << i)
+        };
+        let move_type = HIDDEN_POWER_TYPES[(bits(0) * 15 / 63) as usize];
+        let power = match variant {
+            HiddenPowerVariant::Vanilla => (bits(1) * 40 / 63 + 30) as u8,
+            HiddenPowerVariant::RadicalRed => RADICAL_RED_POWER,
+        };
+        HiddenPower { move_type, power }
+    }
+}
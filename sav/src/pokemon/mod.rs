@@ -0,0 +1,582 @@
+mod condition;
+mod crypt;
+mod hidden_power;
+mod nature;
+mod origin;
+mod stats;
+
+use std::fmt::{self, Debug, Display, Formatter};
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use poke3_common::encoding::parse_string_lossy;
+use poke3_common::rom::{ItemId, Species, Stats};
+
+use crate::pokedex::SpeciesId;
+use crate::section::Gender;
+use crate::text::write_string;
+use crate::trainer::TrainerId;
+use crate::{WriteSaveError, WriteSaveResult};
+
+pub use condition::{ContestStats, Pokerus, StatusCondition};
+pub use hidden_power::{HiddenPower, HiddenPowerVariant};
+pub use nature::Nature;
+pub use origin::{GameOfOrigin, PokeBallId, Ribbons};
+pub use stats::calculate_stats;
+
+const SPECIES_UNOWN: u16 = 201;
+
+/// A Pokémon in the party, which also keeps its level and battle stats.
+#[derive(Clone, Debug)]
+pub struct Pokemon {
+    pub boxed: BoxPokemon,
+
+    pub level: u8,
+    pub current_hp: u16,
+    pub stats: Stats<u16>,
+
+    pub status: StatusCondition,
+
+    raw: RawBytes,
+}
+
+/// A Pokémon as stored in the PC, the party format extends it with battle data.
+#[derive(Clone, Debug)]
+pub struct BoxPokemon {
+    /// Personality value, which determines nature, gender, shininess and more
+    pub personality: u32,
+    pub nickname: String,
+    pub species: SpeciesId,
+    pub otname: String,
+    pub ot_id: TrainerId,
+    pub markings: [bool; 4],
+    pub item: Option<ItemId>,
+    pub friendship: u8,
+
+    pub met_location: u8,
+    /// 0 for Pokémon hatched from eggs
+    pub met_level: u8,
+    pub game_of_origin: GameOfOrigin,
+    pub pokeball: PokeBallId,
+    pub ot_gender: Gender,
+    pub ribbons: Ribbons,
+    /// Set for event Pokémon, Mew and Deoxys only obey their trainer if it is
+    pub fateful_encounter: bool,
+
+    pub experience: u32,
+    pub moves: [Move; 4],
+
+    pub evs: Stats<u8>,
+    pub ivs: Stats<u8>,
+    pub contest: ContestStats,
+    pub pokerus: Pokerus,
+
+    pub is_egg: bool,
+    pub ability: AbilityIndex,
+
+    pub checksum: ChecksumStatus,
+
+    /// Decoded data, written back with the parsed fields on top of it
+    raw: RawBytes,
+}
+
+/// Original data of a Pokémon, so that fields we don't parse survive writing it back.
+#[derive(Clone)]
+struct RawBytes(Vec<u8>);
+
+impl Debug for RawBytes {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "[{} bytes]", self.0.len())
+    }
+}
+
+/// Result of checking the data checksum stored in a Pokémon's header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChecksumStatus {
+    Valid,
+    Mismatch {
+        expected: u16,
+        stored: u16,
+    },
+    /// The game flagged the Pokémon as a Bad Egg, usually after finding a checksum mismatch itself
+    BadEgg,
+}
+
+/// Where a Pokémon is kept in the save.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PokemonSlot {
+    Party(usize),
+    Box { box_index: usize, slot: usize },
+    DayCare(usize),
+}
+
+#[derive(Default, Clone, Copy, Debug)]
+pub struct Move {
+    pub id: u16,
+    pub pp: u8,
+    pub pp_bonus: u8,
+}
+
+/// How the growth, attacks, EVs and misc substructures of a Pokémon are stored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PokemonFormat {
+    /// Encrypted and shuffled according to the personality value, as in the original games
+    Vanilla,
+    /// Unencrypted and always in the same order
+    Cfru,
+}
+
+/// Which of its species' abilities a Pokémon has. The vanilla games store the slot in the IVs word,
+/// CFRU uses that bit for the hidden ability and takes the slot from the personality value instead.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AbilityIndex {
+    First,
+    Second,
+    Hidden,
+}
+
+impl Pokemon {
+    pub const SIZE: usize = BoxPokemon::SIZE + 4 + 2 + 2 + 12;
+
+    pub fn from_bytes(data: &[u8], format: PokemonFormat) -> Self {
+        let boxed = BoxPokemon::from_bytes(&data[..BoxPokemon::SIZE], format);
+        let data = &data[BoxPokemon::SIZE..];
+
+        let level = data[4];
+        let current_hp = LittleEndian::read_u16(&data[6..]);
+        let stats = Stats {
+            hp: LittleEndian::read_u16(&data[8..]),
+            atk: LittleEndian::read_u16(&data[10..]),
+            def: LittleEndian::read_u16(&data[12..]),
+            spe: LittleEndian::read_u16(&data[14..]),
+            spa: LittleEndian::read_u16(&data[16..]),
+            spd: LittleEndian::read_u16(&data[18..]),
+        };
+
+        Pokemon {
+            boxed,
+
+            level,
+            current_hp,
+            stats,
+
+            status: StatusCondition::from_raw(LittleEndian::read_u32(data)),
+
+            raw: RawBytes(data.to_vec()),
+        }
+    }
+
+    /// Stats calculated from the Pokémon's species, IVs, EVs, nature and level.
+    pub fn expected_stats(&self, species: &Species) -> Stats<u16> {
+        self.boxed.stats(species, self.level)
+    }
+
+    /// Whether the stored stats match the calculated ones. The games only update stats on level
+    /// up and a few other occasions, so e.g. EVs gained since then cause mismatches.
+    pub fn stats_match(&self, species: &Species) -> bool {
+        self.stats == self.expected_stats(species)
+    }
+
+    /// Writes the Pokémon in the party format, recalculating its checksum.
+    pub fn write_bytes(&self, data: &mut [u8], format: PokemonFormat) -> WriteSaveResult<()> {
+        self.boxed
+            .write_bytes(&mut data[..BoxPokemon::SIZE], format)?;
+        let data = &mut data[BoxPokemon::SIZE..];
+        data.copy_from_slice(&self.raw.0);
+
+        let status = (LittleEndian::read_u32(data) & !0xFF) | self.status.to_raw();
+        LittleEndian::write_u32(data, status);
+        data[4] = self.level;
+        LittleEndian::write_u16(&mut data[6..], self.current_hp);
+        LittleEndian::write_u16(&mut data[8..], self.stats.hp);
+        LittleEndian::write_u16(&mut data[10..], self.stats.atk);
+        LittleEndian::write_u16(&mut data[12..], self.stats.def);
+        LittleEndian::write_u16(&mut data[14..], self.stats.spe);
+        LittleEndian::write_u16(&mut data[16..], self.stats.spa);
+        LittleEndian::write_u16(&mut data[18..], self.stats.spd);
+        Ok(())
+    }
+}
+
+impl BoxPokemon {
+    pub const SIZE: usize = 32 + 12 + 12 + 12 + 12;
+
+    /// Whether the given box data holds a Pokémon, checked through the unencrypted "has species"
+    /// flag.
+    pub fn is_present(data: &[u8]) -> bool {
+        data[19] & 0b10 != 0
+    }
+
+    pub fn from_bytes(raw: &[u8], format: PokemonFormat) -> Self {
+        let mut buffer = [0u8; BoxPokemon::SIZE];
+        buffer.copy_from_slice(raw);
+        format.decode(&mut buffer);
+        let data = &buffer[..];
+
+        let checksum = if data[19] & 1 != 0 {
+            ChecksumStatus::BadEgg
+        } else {
+            let expected = calculate_checksum(data);
+            let stored = LittleEndian::read_u16(&data[28..]);
+            if expected == stored {
+                ChecksumStatus::Valid
+            } else {
+                ChecksumStatus::Mismatch { expected, stored }
+            }
+        };
+
+        let personality = LittleEndian::read_u32(data);
+        let ot_id = LittleEndian::read_u32(&data[4..]).into();
+        let nickname = parse_string_lossy(&data[8..18]);
+        let otname = parse_string_lossy(&data[20..27]);
+        let raw_markings = data[27];
+        let markings = [
+            raw_markings & (1 << 0) != 0,
+            raw_markings & (1 << 1) != 0,
+            raw_markings & (1 << 2) != 0,
+            raw_markings & (1 << 3) != 0,
+        ];
+        let species = LittleEndian::read_u16(&data[32..]);
+        let item = LittleEndian::read_u16(&data[34..]);
+        let experience = LittleEndian::read_u32(&data[36..]);
+        let pp_bonuses = data[40];
+        let friendship = data[41];
+
+        let move_ids = [
+            LittleEndian::read_u16(&data[44..]),
+            LittleEndian::read_u16(&data[46..]),
+            LittleEndian::read_u16(&data[48..]),
+            LittleEndian::read_u16(&data[50..]),
+        ];
+        let pps = &data[52..56];
+        let mut moves = [Move::default(); 4];
+        for (i, m) in moves.iter_mut().enumerate() {
+            m.id = move_ids[i];
+            m.pp = pps[i];
+            // Each pp bonus takes 2 bits from pp_bonuses
+            m.pp_bonus = (pp_bonuses >> (2 * i)) & 0x03;
+        }
+
+        let evs = Stats {
+            hp: data[56],
+            atk: data[57],
+            def: data[58],
+            spe: data[59],
+            spa: data[60],
+            spd: data[61],
+        };
+
+        let contest = ContestStats {
+            cool: data[62],
+            beauty: data[63],
+            cute: data[64],
+            smart: data[65],
+            tough: data[66],
+            sheen: data[67],
+        };
+        let pokerus = Pokerus::from_raw(data[68]);
+
+        let met_location = data[69];
+        let origins = LittleEndian::read_u16(&data[70..]);
+        let pokeball = match format {
+            PokemonFormat::Vanilla => (origins >> 11) as u8 & 0b1111,
+            // CFRU has more balls than fit in the origins word
+            PokemonFormat::Cfru => data[42],
+        };
+        let raw_ribbons = LittleEndian::read_u32(&data[76..]);
+
+        let raw_ivs = LittleEndian::read_u32(&data[72..]);
+        let ivs = unpack_ivs(raw_ivs);
+        // The final 2 bits are flags for is_egg and the ability
+        let is_egg = (raw_ivs >> 30) & 1 != 0;
+        let ability_bit = (raw_ivs >> 31) != 0;
+        let ability = match format {
+            PokemonFormat::Vanilla if ability_bit => AbilityIndex::Second,
+            PokemonFormat::Cfru if ability_bit => AbilityIndex::Hidden,
+            PokemonFormat::Cfru if personality & 1 != 0 => AbilityIndex::Second,
+            _ => AbilityIndex::First,
+        };
+
+        BoxPokemon {
+            personality,
+            nickname,
+            species: species.into(),
+            otname,
+            ot_id,
+            markings,
+            item: ItemId::new(item),
+            friendship,
+
+            met_location,
+            met_level: origins as u8 & 0x7F,
+            game_of_origin: GameOfOrigin::from_raw((origins >> 7) as u8 & 0b1111),
+            pokeball: pokeball.into(),
+            ot_gender: if origins >> 15 != 0 {
+                Gender::Female
+            } else {
+                Gender::Male
+            },
+            ribbons: Ribbons::from_raw(raw_ribbons),
+            fateful_encounter: raw_ribbons >> 31 != 0,
+
+            experience,
+
+            moves,
+
+            evs,
+            ivs,
+            contest,
+            pokerus,
+
+            is_egg,
+            ability,
+
+            checksum,
+
+            raw: RawBytes(data.to_vec()),
+        }
+    }
+
+    /// Writes the Pokémon in the box format, recalculating its checksum.
+    pub fn write_bytes(&self, out: &mut [u8], format: PokemonFormat) -> WriteSaveResult<()> {
+        let mut buffer = [0u8; BoxPokemon::SIZE];
+        buffer.copy_from_slice(&self.raw.0);
+        let data = &mut buffer[..];
+
+        LittleEndian::write_u32(data, self.personality);
+        LittleEndian::write_u32(&mut data[4..], self.ot_id.value());
+        write_string(&mut data[8..18], &self.nickname, "nickname")?;
+        write_string(&mut data[20..27], &self.otname, "OT name")?;
+        // Keep the "has species" flag in sync, and the egg flag mirrored from the IVs word
+        data[19] = (data[19] & !0b110)
+            | if self.species.0 != 0 { 0b10 } else { 0 }
+            | if self.is_egg { 0b100 } else { 0 };
+        data[27] = self
+            .markings
+            .iter()
+            .enumerate()
+            .fold(
+                data[27] & 0xF0,
+                |acc, (i, marked)| if *marked { acc | (1 << i) } else { acc },
+            );
+
+        LittleEndian::write_u16(&mut data[32..], self.species.0);
+        LittleEndian::write_u16(&mut data[34..], self.item.map_or(0, |i| i.get()));
+        LittleEndian::write_u32(&mut data[36..], self.experience);
+        data[40] = self
+            .moves
+            .iter()
+            .enumerate()
+            .fold(0, |acc, (i, m)| acc | ((m.pp_bonus & 0x03) << (2 * i)));
+        data[41] = self.friendship;
+
+        for (i, m) in self.moves.iter().enumerate() {
+            LittleEndian::write_u16(&mut data[44 + 2 * i..], m.id);
+            data[52 + i] = m.pp;
+        }
+
+        data[56] = self.evs.hp;
+        data[57] = self.evs.atk;
+        data[58] = self.evs.def;
+        data[59] = self.evs.spe;
+        data[60] = self.evs.spa;
+        data[61] = self.evs.spd;
+        data[62] = self.contest.cool;
+        data[63] = self.contest.beauty;
+        data[64] = self.contest.cute;
+        data[65] = self.contest.smart;
+        data[66] = self.contest.tough;
+        data[67] = self.contest.sheen;
+        data[68] = self.pokerus.to_raw();
+
+        let raw_ivs = pack_ivs(&self.ivs)
+            | (self.is_egg as u32) << 30
+            | (self.ability_bit(format)? as u32) << 31;
+        LittleEndian::write_u32(&mut data[72..], raw_ivs);
+
+        data[69] = self.met_location;
+        let ball_bits = match format {
+            PokemonFormat::Vanilla => self.pokeball.0 as u16,
+            PokemonFormat::Cfru => {
+                data[42] = self.pokeball.0;
+                LittleEndian::read_u16(&data[70..]) >> 11
+            }
+        };
+        let origins = (self.met_level as u16 & 0x7F)
+            | (self.game_of_origin.to_raw() as u16 & 0b1111) << 7
+            | (ball_bits & 0b1111) << 11
+            | (self.ot_gender as u16) << 15;
+        LittleEndian::write_u16(&mut data[70..], origins);
+        let raw_ribbons = (LittleEndian::read_u32(&data[76..]) & !(Ribbons::MASK | 1 << 31))
+            | self.ribbons.to_raw()
+            | (self.fateful_encounter as u32) << 31;
+        LittleEndian::write_u32(&mut data[76..], raw_ribbons);
+
+        let checksum = calculate_checksum(data);
+        LittleEndian::write_u16(&mut data[28..], checksum);
+        format.encode(data);
+        out.copy_from_slice(data);
+        Ok(())
+    }
+}
+
+impl BoxPokemon {
+    pub fn nature(&self) -> Nature {
+        Nature::from_personality(self.personality)
+    }
+
+    /// Level derived from the experience, box Pokémon don't store it.
+    pub fn level(&self, species: &Species) -> u8 {
+        species.growth_rate.level_for_exp(self.experience)
+    }
+
+    /// `None` at the maximum level.
+    pub fn exp_to_next_level(&self, species: &Species) -> Option<u32> {
+        species.growth_rate.exp_to_next_level(self.experience)
+    }
+
+    /// Stats the Pokémon would have at the given level.
+    pub fn stats(&self, species: &Species, level: u8) -> Stats<u16> {
+        calculate_stats(
+            &species.base_stats,
+            &self.ivs,
+            &self.evs,
+            self.nature(),
+            level,
+        )
+    }
+
+    pub fn hidden_power(&self, variant: HiddenPowerVariant) -> HiddenPower {
+        HiddenPower::from_ivs(&self.ivs, variant)
+    }
+
+    /// Whether the Pokémon is shiny for a trainer with the given id. The games check against the
+    /// original trainer, i.e. `ot_id`.
+    pub fn is_shiny(&self, trainer_id: &TrainerId) -> bool {
+        let personality = (self.personality >> 16) ^ (self.personality & 0xFFFF);
+        (trainer_id.tid as u32 ^ trainer_id.sid as u32 ^ personality) < 8
+    }
+
+    /// `None` for genderless species.
+    pub fn gender(&self, species: &Species) -> Option<Gender> {
+        species
+            .gender_ratio
+            .is_female(self.personality)
+            .map(|female| if female { Gender::Female } else { Gender::Male })
+    }
+
+    /// Letter of an Unown, from 0 for A to 25 for Z, then 26 for ! and 27 for ?. `None` for other
+    /// species.
+    pub fn unown_form(&self) -> Option<u8> {
+        if self.species.0 != SPECIES_UNOWN {
+            return None;
+        }
+        // Built from the lowest 2 bits of each byte of the personality value
+        let form = (0..4).fold(0, |acc, i| {
+            acc | (self.personality >> (8 * i) & 0b11) << (2 * i)
+        });
+        Some((form % 28) as u8)
+    }
+
+    /// Bit 31 of the IVs word for the ability, see `AbilityIndex`.
+    fn ability_bit(&self, format: PokemonFormat) -> WriteSaveResult<bool> {
+        match (format, self.ability) {
+            (PokemonFormat::Vanilla, AbilityIndex::Hidden) => Err(WriteSaveError::InvalidData(
+                "Hidden abilities aren't supported by the vanilla Pokémon format".to_string(),
+            )),
+            (PokemonFormat::Vanilla, ability) => Ok(ability == AbilityIndex::Second),
+            (PokemonFormat::Cfru, AbilityIndex::Hidden) => Ok(true),
+            (PokemonFormat::Cfru, ability) => {
+                if (ability == AbilityIndex::Second) == (self.personality & 1 != 0) {
+                    Ok(false)
+                } else {
+                    Err(WriteSaveError::InvalidData(format!(
+                        "Ability {:?} doesn't match personality value 0x{:08X}",
+                        ability, self.personality
+                    )))
+                }
+            }
+        }
+    }
+}
+
+impl ChecksumStatus {
+    pub fn is_valid(self) -> bool {
+        self == ChecksumStatus::Valid
+    }
+}
+
+impl Display for ChecksumStatus {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ChecksumStatus::Valid => write!(f, "valid checksum"),
+            ChecksumStatus::Mismatch { expected, stored } => write!(
+                f,
+                "invalid checksum, expected 0x{:04X}, got 0x{:04X}",
+                expected, stored
+            ),
+            ChecksumStatus::BadEgg => write!(f, "flagged as a Bad Egg"),
+        }
+    }
+}
+
+impl Display for PokemonSlot {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            PokemonSlot::Party(i) => write!(f, "party slot {}", i + 1),
+            PokemonSlot::Box { box_index, slot } => {
+                write!(f, "box {} slot {}", box_index + 1, slot + 1)
+            }
+            PokemonSlot::DayCare(i) => write!(f, "Day Care slot {}", i + 1),
+        }
+    }
+}
+
+impl PokemonFormat {
+    /// Turns stored box data into the unencrypted growth/attacks/EVs/misc layout, in place.
+    pub fn decode(self, data: &mut [u8]) {
+        match self {
+            PokemonFormat::Vanilla => crypt::decrypt(data),
+            PokemonFormat::Cfru => {}
+        }
+    }
+
+    /// Inverse of `decode`, turns unencrypted box data into the stored layout, in place.
+    pub fn encode(self, data: &mut [u8]) {
+        match self {
+            PokemonFormat::Vanilla => crypt::encrypt(data),
+            PokemonFormat::Cfru => {}
+        }
+    }
+}
+
+/// Reads IVs from the lowest 30 bits of a word, each one is kept as 5 bits.
+pub fn unpack_ivs(raw: u32) -> Stats<u8> {
+    Stats {
+        hp: (raw & 0b11111) as u8,
+        atk: ((raw >> 5) & 0b11111) as u8,
+        def: ((raw >> 10) & 0b11111) as u8,
+        spe: ((raw >> 15) & 0b11111) as u8,
+        spa: ((raw >> 20) & 0b11111) as u8,
+        spd: ((raw >> 25) & 0b11111) as u8,
+    }
+}
+
+/// Inverse of `unpack_ivs`, leaves the top 2 bits unset.
+pub fn pack_ivs(ivs: &Stats<u8>) -> u32 {
+    (ivs.hp as u32 & 0b11111)
+        | (ivs.atk as u32 & 0b11111) << 5
+        | (ivs.def as u32 & 0b11111) << 10
+        | (ivs.spe as u32 & 0b11111) << 15
+        | (ivs.spa as u32 & 0b11111) << 20
+        | (ivs.spd as u32 & 0b11111) << 25
+}
+
+/// Sum of every 16-bit word in the decoded substructures, which the game stores in the header.
+pub fn calculate_checksum(data: &[u8]) -> u16 {
+    data[32..BoxPokemon::SIZE]
+        .chunks(2)
+        .fold(0u16, |acc, word| {
+            acc.wrapping_add(LittleEndian::read_u16(word))
+        })
+}
@@ -0,0 +1,85 @@
+use poke3_common::rom::Stats;
+
+/// Natures in the order the games index them, personality % 25.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Nature {
+    Hardy,
+    Lonely,
+    Brave,
+    Adamant,
+    Naughty,
+    Bold,
+    Docile,
+    Relaxed,
+    Impish,
+    Lax,
+    Timid,
+    Hasty,
+    Serious,
+    Jolly,
+    Naive,
+    Modest,
+    Mild,
+    Quiet,
+    Bashful,
+    Rash,
+    Calm,
+    Gentle,
+    Sassy,
+    Careful,
+    Quirky,
+}
+
+const NATURES: [Nature; 25] = [
+    Nature::Hardy,
+    Nature::Lonely,
+    Nature::Brave,
+    Nature::Adamant,
+    Nature::Naughty,
+    Nature::Bold,
+    Nature::Docile,
+    Nature::Relaxed,
+    Nature::Impish,
+    Nature::Lax,
+    Nature::Timid,
+    Nature::Hasty,
+    Nature::Serious,
+    Nature::Jolly,
+    Nature::Naive,
+    Nature::Modest,
+    Nature::Mild,
+    Nature::Quiet,
+    Nature::Bashful,
+    Nature::Rash,
+    Nature::Calm,
+    Nature::Gentle,
+    Nature::Sassy,
+    Nature::Careful,
+    Nature::Quirky,
+];
+
+impl Nature {
+    pub fn from_personality(personality: u32) -> Self {
+        NATURES[(personality % 25) as usize]
+    }
+
+    /// Percentage each stat is multiplied by, 110 for the raised stat and 90 for the lowered one.
+    /// Natures raising and lowering the same stat are neutral.
+    pub fn stat_modifiers(self) -> Stats<u16> {
+        // Natures are ordered by the raised stat then the lowered one, Atk, Def, Spe, SpA and SpD
+        let index = self as usize;
+        let mut modifiers = [100; 5];
+        if index / 5 != index % 5 {
+            modifiers[index / 5] = 110;
+            modifiers[index % 5] = 90;
+        }
+        Stats {
+            hp: 100,
+            atk: modifiers[0],
+            def: modifiers[1],
+            spe: modifiers[2],
+            spa: modifiers[3],
+            spd: modifiers[4],
+        }
+    }
+}
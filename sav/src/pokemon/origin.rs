@@ -0,0 +1,149 @@
+use std::fmt::{self, Display, Formatter};
+
+/// Game a Pokémon was caught or hatched in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameOfOrigin {
+    Sapphire,
+    Ruby,
+    Emerald,
+    FireRed,
+    LeafGreen,
+    ColosseumXd,
+    Other(u8),
+}
+
+/// Index of a Poké Ball. CFRU hacks add balls after the vanilla ones.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PokeBallId(pub u8);
+
+/// Ribbons won by a Pokémon.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Ribbons {
+    /// Highest rank won in each contest category, from 0 for none to 4 for Master rank
+    pub cool: u8,
+    pub beauty: u8,
+    pub cute: u8,
+    pub smart: u8,
+    pub tough: u8,
+    pub champion: bool,
+    pub winning: bool,
+    pub victory: bool,
+    pub artist: bool,
+    pub effort: bool,
+    pub marine: bool,
+    pub land: bool,
+    pub sky: bool,
+    pub country: bool,
+    pub national: bool,
+    pub earth: bool,
+    pub world: bool,
+}
+
+impl GameOfOrigin {
+    pub fn from_raw(raw: u8) -> Self {
+        match raw {
+            1 => GameOfOrigin::Sapphire,
+            2 => GameOfOrigin::Ruby,
+            3 => GameOfOrigin::Emerald,
+            4 => GameOfOrigin::FireRed,
+            5 => GameOfOrigin::LeafGreen,
+            15 => GameOfOrigin::ColosseumXd,
+            other => GameOfOrigin::Other(other),
+        }
+    }
+
+    pub fn to_raw(self) -> u8 {
+        match self {
+            GameOfOrigin::Sapphire => 1,
+            GameOfOrigin::Ruby => 2,
+            GameOfOrigin::Emerald => 3,
+            GameOfOrigin::FireRed => 4,
+            GameOfOrigin::LeafGreen => 5,
+            GameOfOrigin::ColosseumXd => 15,
+            GameOfOrigin::Other(other) => other,
+        }
+    }
+}
+
+impl PokeBallId {
+    pub const MASTER_BALL: PokeBallId = PokeBallId(1);
+    pub const ULTRA_BALL: PokeBallId = PokeBallId(2);
+    pub const GREAT_BALL: PokeBallId = PokeBallId(3);
+    pub const POKE_BALL: PokeBallId = PokeBallId(4);
+    pub const SAFARI_BALL: PokeBallId = PokeBallId(5);
+    pub const NET_BALL: PokeBallId = PokeBallId(6);
+    pub const DIVE_BALL: PokeBallId = PokeBallId(7);
+    pub const NEST_BALL: PokeBallId = PokeBallId(8);
+    pub const REPEAT_BALL: PokeBallId = PokeBallId(9);
+    pub const TIMER_BALL: PokeBallId = PokeBallId(10);
+    pub const LUXURY_BALL: PokeBallId = PokeBallId(11);
+    pub const PREMIER_BALL: PokeBallId = PokeBallId(12);
+}
+
+impl Display for PokeBallId {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl From<u8> for PokeBallId {
+    fn from(id: u8) -> Self {
+        PokeBallId(id)
+    }
+}
+
+impl Ribbons {
+    /// Mask of the bits used by `to_raw`.
+    pub const MASK: u32 = (1 << 27) - 1;
+
+    pub fn from_raw(raw: u32) -> Self {
+        let rank = |shift: u32| ((raw >> shift) & 0b111) as u8;
+        let flag = |bit: u32| raw & (1 << bit) != 0;
+        Ribbons {
+            cool: rank(0),
+            beauty: rank(3),
+            cute: rank(6),
+            smart: rank(9),
+            tough: rank(12),
+            champion: flag(15),
+            winning: flag(16),
+            victory: flag(17),
+            artist: flag(18),
+            effort: flag(19),
+            marine: flag(20),
+            land: flag(21),
+            sky: flag(22),
+            country: flag(23),
+            national: flag(24),
+            earth: flag(25),
+            world: flag(26),
+        }
+    }
+
+    /// Ribbon bits of the ribbons word, the remaining bits are left to the caller.
+    pub fn to_raw(self) -> u32 {
+        let ranks = [self.cool, self.beauty, self.cute, self.smart, self.tough];
+        let flags = [
+            self.champion,
+            self.winning,
+            self.victory,
+            self.artist,
+            self.effort,
+            self.marine,
+            self.land,
+            self.sky,
+            self.country,
+            self.national,
+            self.earth,
+            self.world,
+        ];
+        let raw = ranks
+            .iter()
+            .enumerate()
+            .fold(0, |acc, (i, rank)| acc | (*rank as u32 & 0b111) << (3 * i));
+        flags
+            .iter()
+            .enumerate()
+            .fold(raw, |acc, (i, flag)| acc | (*flag as u32) << (15 + i))
+    }
+}
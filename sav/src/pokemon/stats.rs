@@ -0,0 +1,33 @@
+use poke3_common::rom::Stats;
+
+use super::Nature;
+
+/// Stats of a Pokémon as calculated by the games.
+pub fn calculate_stats(
+    base_stats: &Stats<u8>,
+    ivs: &Stats<u8>,
+    evs: &Stats<u8>,
+    nature: Nature,
+    level: u8,
+) -> Stats<u16> {
+    let level = level as u32;
+    let modifiers = nature.stat_modifiers();
+    let stat =
+        |base: u8, iv: u8, ev: u8| (2 * base as u32 + iv as u32 + ev as u32 / 4) * level / 100;
+    let other =
+        |base, iv, ev, modifier: u16| ((stat(base, iv, ev) + 5) * modifier as u32 / 100) as u16;
+
+    Stats {
+        // Shedinja is the only species with a base HP of 1, and always has 1 HP
+        hp: if base_stats.hp == 1 {
+            1
+        } else {
+            (stat(base_stats.hp, ivs.hp, evs.hp) + level + 10) as u16
+        },
+        atk: other(base_stats.atk, ivs.atk, evs.atk, modifiers.atk),
+        def: other(base_stats.def, ivs.def, evs.def, modifiers.def),
+        spe: other(base_stats.spe, ivs.spe, evs.spe, modifiers.spe),
+        spa: other(base_stats.spa, ivs.spa, evs.spa, modifiers.spa),
+        spd: other(base_stats.spd, ivs.spd, evs.spd, modifiers.spd),
+    }
+}
@@ -0,0 +1,121 @@
+use crate::events::Flags;
+use crate::version::GameVersion;
+
+/// Flags used to track progress through the game. Trainers are identified by their index in the
+/// ROM's trainer table, each one has a flag set when it's defeated.
+#[derive(Debug)]
+pub struct ProgressFlags {
+    pub trainer_flags_start: u16,
+    pub trainer_count: u16,
+    pub badges: [u16; 8],
+    /// Trainer ids of each Elite Four member, including rematches
+    pub elite_four: [&'static [u16]; 4],
+    /// Trainer ids of the Champion, one for each starter the rival can have plus rematches
+    pub champion: &'static [u16],
+    pub milestones: &'static [(Milestone, u16)],
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Milestone {
+    StarterObtained,
+    PokedexObtained,
+    RunningShoes,
+    HallOfFame,
+}
+
+/// Progress through the game derived from the save's flags.
+#[derive(Clone, Debug)]
+pub struct Progress {
+    pub badges: [bool; 8],
+    /// Lorelei, Bruno, Agatha and Lance
+    pub elite_four: [bool; 4],
+    pub champion: bool,
+    /// Ids of every defeated trainer, in increasing order
+    pub defeated_trainers: Vec<u16>,
+    pub milestones: Vec<Milestone>,
+}
+
+pub const FIRE_RED_LEAF_GREEN: ProgressFlags = ProgressFlags {
+    trainer_flags_start: 0x0500,
+    trainer_count: 743,
+    badges: [
+        0x0820, 0x0821, 0x0822, 0x0823, 0x0824, 0x0825, 0x0826, 0x0827,
+    ],
+    elite_four: [&[410, 735], &[411, 736], &[412, 737], &[413, 738]],
+    champion: &[438, 439, 440, 739, 740, 741],
+    milestones: &[
+        (Milestone::StarterObtained, 0x0828),
+        (Milestone::PokedexObtained, 0x0829),
+        (Milestone::HallOfFame, 0x082C),
+        (Milestone::RunningShoes, 0x082F),
+    ],
+};
+
+/// CFRU keeps the vanilla flags, but hacks add trainers up to the start of the system flags.
+pub const CFRU: ProgressFlags = ProgressFlags {
+    trainer_count: 0x0300,
+    ..FIRE_RED_LEAF_GREEN
+};
+
+impl ProgressFlags {
+    /// `None` for games whose flags aren't known.
+    pub fn for_version(version: GameVersion) -> Option<&'static Self> {
+        match version {
+            GameVersion::FireRedLeafGreen => Some(&FIRE_RED_LEAF_GREEN),
+            GameVersion::Cfru => Some(&CFRU),
+            GameVersion::RubySapphire | GameVersion::Emerald => None,
+        }
+    }
+
+    pub fn trainer_flag(&self, trainer: u16) -> Option<u16> {
+        if trainer < self.trainer_count {
+            Some(self.trainer_flags_start + trainer)
+        } else {
+            None
+        }
+    }
+}
+
+impl Progress {
+    pub fn from_flags(flags: &Flags, progress_flags: &ProgressFlags) -> Self {
+        let is_set = |flag: u16| flags.get(flag).unwrap_or(false);
+        let defeated = |trainer: &u16| match progress_flags.trainer_flag(*trainer) {
+            Some(flag) => is_set(flag),
+            None => false,
+        };
+
+        let mut badges = [false; 8];
+        for (badge, flag) in badges.iter_mut().zip(&progress_flags.badges) {
+            *badge = is_set(*flag);
+        }
+        let mut elite_four = [false; 4];
+        for (member, trainers) in elite_four.iter_mut().zip(&progress_flags.elite_four) {
+            *member = trainers.iter().any(defeated);
+        }
+
+        Progress {
+            badges,
+            elite_four,
+            champion: progress_flags.champion.iter().any(defeated),
+            defeated_trainers: (0..progress_flags.trainer_count).filter(defeated).collect(),
+            milestones: progress_flags
+                .milestones
+                .iter()
+                .filter(|(_, flag)| is_set(*flag))
+                .map(|(milestone, _)| *milestone)
+                .collect(),
+        }
+    }
+
+    pub fn badge_count(&self) -> usize {
+        self.badges.iter().filter(|b| **b).count()
+    }
+
+    pub fn has_defeated(&self, trainer: u16) -> bool {
+        self.defeated_trainers.binary_search(&trainer).is_ok()
+    }
+
+    pub fn has_milestone(&self, milestone: Milestone) -> bool {
+        self.milestones.contains(&milestone)
+    }
+}
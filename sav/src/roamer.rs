@@ -0,0 +1,60 @@
+use byteorder::{ByteOrder, LittleEndian};
+
+use poke3_common::rom::Stats;
+
+use crate::pokedex::SpeciesId;
+use crate::pokemon::{pack_ivs, unpack_ivs, ContestStats, StatusCondition};
+
+/// The roaming legendary, Raikou, Entei or Suicune in FireRed/LeafGreen and Latios or Latias in
+/// Ruby/Sapphire/Emerald. Its current map is only kept in memory, the game picks a new one after
+/// loading.
+#[derive(Clone, Debug)]
+pub struct Roamer {
+    pub species: SpeciesId,
+    pub personality: u32,
+    pub level: u8,
+    pub current_hp: u16,
+    pub status: StatusCondition,
+    pub ivs: Stats<u8>,
+    pub contest: ContestStats,
+    /// Unset before the roamer is released and after it's caught or defeated
+    pub active: bool,
+}
+
+impl Roamer {
+    pub fn from_bytes(data: &[u8]) -> Self {
+        Roamer {
+            ivs: unpack_ivs(LittleEndian::read_u32(data)),
+            personality: LittleEndian::read_u32(&data[4..]),
+            species: LittleEndian::read_u16(&data[8..]).into(),
+            current_hp: LittleEndian::read_u16(&data[10..]),
+            level: data[12],
+            status: StatusCondition::from_raw(data[13] as u32),
+            contest: ContestStats {
+                cool: data[14],
+                beauty: data[15],
+                cute: data[16],
+                smart: data[17],
+                tough: data[18],
+                sheen: 0,
+            },
+            active: data[19] != 0,
+        }
+    }
+
+    pub fn write_bytes(&self, data: &mut [u8]) {
+        let raw_ivs = (LittleEndian::read_u32(data) & !((1 << 30) - 1)) | pack_ivs(&self.ivs);
+        LittleEndian::write_u32(data, raw_ivs);
+        LittleEndian::write_u32(&mut data[4..], self.personality);
+        LittleEndian::write_u16(&mut data[8..], self.species.0);
+        LittleEndian::write_u16(&mut data[10..], self.current_hp);
+        data[12] = self.level;
+        data[13] = self.status.to_raw() as u8;
+        data[14] = self.contest.cool;
+        data[15] = self.contest.beauty;
+        data[16] = self.contest.cute;
+        data[17] = self.contest.smart;
+        data[18] = self.contest.tough;
+        data[19] = self.active as u8;
+    }
+}
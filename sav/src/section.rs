@@ -0,0 +1,443 @@
+use std::convert::TryFrom;
+use std::io::{Read, Seek, Write};
+use std::time::Duration;
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use poke3_common::encoding::{parse_string_lossy, STRING_TERMINATOR};
+use poke3_common::rom::{Rom, Stats};
+
+use crate::bag::{read_pocket, write_pocket, Bag, ItemSlot};
+use crate::blocks::SaveBlocks;
+use crate::daycare::DayCare;
+use crate::events::{Flags, Vars};
+use crate::game_stats::{
+    read_link_battle_records, write_link_battle_records, GameStats, LinkBattleOpponent,
+    TrainerCardStars,
+};
+use crate::layout::Layout;
+use crate::location::Location;
+use crate::pokedex::Pokedex;
+use crate::pokemon::{BoxPokemon, Pokemon, PokemonSlot};
+use crate::progress::{Progress, ProgressFlags};
+use crate::roamer::Roamer;
+use crate::storage::PcStorage;
+use crate::text::write_string;
+use crate::trainer::TrainerId;
+use crate::version::GameVersion;
+use crate::{LoadSaveError, LoadSaveResult, WriteSaveError, WriteSaveResult};
+
+const PLAYER_NAME_LENGTH: usize = 7;
+pub const PARTY_SIZE: usize = 6;
+
+#[derive(Debug, Clone)]
+pub struct Save {
+    pub version: GameVersion,
+    pub player_name: String,
+    pub gender: Gender,
+    pub trainer_id: TrainerId,
+    pub play_time: Duration,
+    pub location: Location,
+    pub money: u32,
+    pub coins: u16,
+    /// `None` for games whose bag layout isn't supported. This includes CFRU hacks such as Radical
+    /// Red, which store their expanded pockets outside of the vanilla structures.
+    pub bag: Option<Bag>,
+    pub pc_items: Vec<ItemSlot>,
+    pub flags: Flags,
+    pub vars: Vars,
+    pub pokedex: Pokedex,
+    pub party: Vec<Pokemon>,
+    pub pc: PcStorage,
+    /// `None` for games whose Day Care isn't supported
+    pub daycare: Option<DayCare>,
+    /// `None` for games whose roamer isn't supported
+    pub roamer: Option<Roamer>,
+    pub game_stats: GameStats,
+    /// Last link battle opponents, `None` for games where they aren't supported
+    pub link_battle_records: Option<Vec<LinkBattleOpponent>>,
+
+    /// Data the save was loaded from, used as base when writing it back
+    blocks: SaveBlocks,
+}
+
+/// Settings for how a save is interpreted.
+#[derive(Debug, Clone, Default)]
+pub struct LoadOptions {
+    /// Game the save comes from, detected from the save data if not set
+    pub version: Option<GameVersion>,
+    /// Fail on Pokémon with a bad checksum or flagged as Bad Eggs instead of loading them
+    pub strict_checksums: bool,
+}
+
+struct SaveBlock2 {
+    player_name: String,
+    gender: Gender,
+    trainer_id: TrainerId,
+    play_time: Duration,
+}
+
+struct SaveBlock1 {
+    location: Location,
+    money: u32,
+    coins: u16,
+    bag: Option<Bag>,
+    pc_items: Vec<ItemSlot>,
+    daycare: Option<DayCare>,
+    roamer: Option<Roamer>,
+    game_stats: GameStats,
+    link_battle_records: Option<Vec<LinkBattleOpponent>>,
+    flags: Flags,
+    vars: Vars,
+    party: Vec<Pokemon>,
+}
+
+/// A party Pokémon whose stored stats differ from the calculated ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatMismatch {
+    pub slot: PokemonSlot,
+    pub stored: Stats<u16>,
+    pub expected: Stats<u16>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Gender {
+    Male,
+    Female,
+}
+
+impl Save {
+    pub fn read<R: Read + Seek>(reader: R) -> LoadSaveResult<Self> {
+        Save::read_with_options(reader, &LoadOptions::default())
+    }
+
+    pub fn read_with_options<R: Read + Seek>(
+        reader: R,
+        options: &LoadOptions,
+    ) -> LoadSaveResult<Self> {
+        Save::from_blocks(&SaveBlocks::read(reader, options.version)?, options)
+    }
+
+    pub fn from_blocks(blocks: &SaveBlocks, options: &LoadOptions) -> LoadSaveResult<Self> {
+        let layout = blocks.version.layout();
+        let block2 = SaveBlock2::from_bytes(&blocks.save_block_2)?;
+        let block1 = SaveBlock1::from_bytes(&blocks.save_block_1, layout, blocks.security_key())?;
+        let pc = PcStorage::from_bytes(&blocks.pokemon_storage, layout.pokemon_format)?;
+        let pokedex = Pokedex::read(blocks, &block1.flags, &block1.vars);
+
+        let save = Save {
+            version: blocks.version,
+            player_name: block2.player_name,
+            gender: block2.gender,
+            trainer_id: block2.trainer_id,
+            play_time: block2.play_time,
+            location: block1.location,
+            money: block1.money,
+            coins: block1.coins,
+            bag: block1.bag,
+            pc_items: block1.pc_items,
+            flags: block1.flags,
+            vars: block1.vars,
+            pokedex,
+            party: block1.party,
+            pc,
+            daycare: block1.daycare,
+            roamer: block1.roamer,
+            game_stats: block1.game_stats,
+            link_battle_records: block1.link_battle_records,
+
+            blocks: blocks.clone(),
+        };
+        if options.strict_checksums {
+            if let Some((slot, pokemon)) = save.pokemon().find(|(_, p)| !p.checksum.is_valid()) {
+                return Err(LoadSaveError::InvalidPokemon {
+                    slot,
+                    checksum: pokemon.checksum,
+                });
+            }
+        }
+        Ok(save)
+    }
+
+    /// Saves like the game does: into the slot that wasn't loaded, with a bumped counter and
+    /// rotated sectors, which leaves the loaded slot as a backup. Since only one slot is written
+    /// the writer must hold the file the save was read from.
+    pub fn write<W: Write + Seek>(&mut self, writer: W) -> WriteSaveResult<()> {
+        let mut blocks = self.to_blocks()?;
+        blocks.advance();
+        blocks.write(writer)?;
+        self.blocks = blocks;
+        Ok(())
+    }
+
+    /// Save blocks with every field written on top of the data the save was loaded from.
+    pub fn to_blocks(&self) -> WriteSaveResult<SaveBlocks> {
+        let mut blocks = self.blocks.clone();
+        let layout = blocks.version.layout();
+        self.write_save_block_2(&mut blocks.save_block_2)?;
+        let key = blocks.security_key();
+        self.write_save_block_1(&mut blocks.save_block_1, layout, key)?;
+        self.pokedex.write(&mut blocks);
+        self.pc
+            .write_bytes(&mut blocks.pokemon_storage, layout.pokemon_format)?;
+        Ok(blocks)
+    }
+
+    fn write_save_block_2(&self, data: &mut [u8]) -> WriteSaveResult<()> {
+        if parse_string_lossy(&data[..PLAYER_NAME_LENGTH + 1]) != self.player_name {
+            write_string(
+                &mut data[..PLAYER_NAME_LENGTH],
+                &self.player_name,
+                "player name",
+            )?;
+            data[PLAYER_NAME_LENGTH] = STRING_TERMINATOR;
+        }
+        data[8] = self.gender as u8;
+        LittleEndian::write_u32(&mut data[10..], self.trainer_id.value());
+
+        let total_seconds = self.play_time.as_secs();
+        let hours = (total_seconds / (60 * 60)).min(u16::MAX as u64) as u16;
+        LittleEndian::write_u16(&mut data[14..], hours);
+        data[16] = (total_seconds / 60 % 60) as u8;
+        data[17] = (total_seconds % 60) as u8;
+        Ok(())
+    }
+
+    fn write_save_block_1(
+        &self,
+        data: &mut [u8],
+        layout: &Layout,
+        key: u32,
+    ) -> WriteSaveResult<()> {
+        self.location.write_bytes(data);
+        LittleEndian::write_u32(&mut data[layout.money..], self.money ^ key);
+        LittleEndian::write_u16(&mut data[layout.coins..], self.coins ^ key as u16);
+        write_pocket(data, &layout.pc_items, &self.pc_items, 0, "PC items")?;
+        self.write_events(data, layout);
+        match (&self.daycare, layout.daycare) {
+            (Some(daycare), Some(offset)) => {
+                daycare.write_bytes(&mut data[offset..], layout.pokemon_format)?
+            }
+            (None, _) => {}
+            (Some(_), None) => return Err(unsupported("Day Care")),
+        }
+        match (&self.roamer, layout.roamer) {
+            (Some(roamer), Some(offset)) => roamer.write_bytes(&mut data[offset..]),
+            (None, _) => {}
+            (Some(_), None) => return Err(unsupported("Roamer")),
+        }
+        self.game_stats.write_bytes(
+            &mut data[layout.game_stats..layout.game_stats + layout.game_stats_count * 4],
+            key,
+        );
+        match (&self.link_battle_records, layout.link_battle_records) {
+            (Some(records), Some(offset)) => {
+                write_link_battle_records(&mut data[offset..], records)?
+            }
+            (None, _) => {}
+            (Some(_), None) => return Err(unsupported("Link battle records")),
+        }
+        match (&self.bag, &layout.bag) {
+            (Some(bag), Some(bag_layout)) => bag.write_bytes(data, bag_layout, key as u16)?,
+            (None, _) => {}
+            (Some(_), None) => return Err(unsupported("Bag")),
+        }
+
+        if self.party.len() > PARTY_SIZE {
+            return Err(WriteSaveError::InvalidData(format!(
+                "Party has {} Pokémon, expected at most {}",
+                self.party.len(),
+                PARTY_SIZE
+            )));
+        }
+        data[layout.party] = self.party.len() as u8;
+        let start = layout.party + 4;
+        let party_data = data[start..start + PARTY_SIZE * Pokemon::SIZE].chunks_mut(Pokemon::SIZE);
+        for (i, raw) in party_data.enumerate() {
+            match self.party.get(i) {
+                Some(pokemon) => pokemon.write_bytes(raw, layout.pokemon_format)?,
+                None => raw.iter_mut().for_each(|b| *b = 0),
+            }
+        }
+        Ok(())
+    }
+
+    fn write_events(&self, data: &mut [u8], layout: &Layout) {
+        self.flags
+            .write_bytes(&mut data[layout.flags..layout.flags + layout.flags_size]);
+        self.vars
+            .write_bytes(&mut data[layout.vars..layout.vars + layout.vars_count * 2]);
+    }
+
+    /// Unlocks or locks the national dex, which needs both a pokedex byte and an event flag and var.
+    pub fn set_national_dex(&mut self, enabled: bool) {
+        let national = &self.blocks.version.layout().national_dex;
+        self.pokedex.national_dex = enabled;
+        self.flags.set(national.flag, enabled);
+        self.vars
+            .set(national.var, if enabled { national.var_value } else { 0 });
+    }
+
+    /// Badges, defeated trainers and story milestones, `None` for games whose flags aren't known.
+    pub fn progress(&self) -> Option<Progress> {
+        ProgressFlags::for_version(self.version)
+            .map(|progress_flags| Progress::from_flags(&self.flags, progress_flags))
+    }
+
+    /// Trainer card stars the save has earned.
+    pub fn trainer_card_stars(&self) -> TrainerCardStars {
+        let caught = |id: &u16| match self.pokedex.get((*id).into()) {
+            Some(entry) => entry.caught,
+            None => false,
+        };
+        TrainerCardStars {
+            hall_of_fame: self.game_stats.hall_of_fame_time().is_some(),
+            pokedex_complete: TrainerCardStars::pokedex_species(self.version)
+                .iter()
+                .all(caught),
+        }
+    }
+
+    /// Party Pokémon whose stored stats don't match the ones calculated from the ROM's base stats.
+    /// Eggs and Pokémon whose species isn't in the ROM are skipped.
+    pub fn stat_mismatches(&self, rom: &Rom) -> Vec<StatMismatch> {
+        self.party
+            .iter()
+            .enumerate()
+            .filter(|(_, pokemon)| !pokemon.boxed.is_egg)
+            .filter_map(|(i, pokemon)| {
+                let species = pokemon.boxed.species.resolve(rom)?;
+                let expected = pokemon.expected_stats(species);
+                if pokemon.stats == expected {
+                    None
+                } else {
+                    Some(StatMismatch {
+                        slot: PokemonSlot::Party(i),
+                        stored: pokemon.stats,
+                        expected,
+                    })
+                }
+            })
+            .collect()
+    }
+
+    /// Every Pokémon in the party, the PC and the Day Care.
+    pub fn pokemon(&self) -> impl Iterator<Item = (PokemonSlot, &BoxPokemon)> {
+        let party = self
+            .party
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (PokemonSlot::Party(i), &p.boxed));
+        let pc = self
+            .pc
+            .iter()
+            .map(|(box_index, slot, p)| (PokemonSlot::Box { box_index, slot }, p));
+        let daycare = self.daycare.iter().flat_map(|daycare| {
+            daycare.pokemon.iter().enumerate().filter_map(|(i, slot)| {
+                slot.as_ref().map(|p| (PokemonSlot::DayCare(i), &p.pokemon))
+            })
+        });
+        party.chain(pc).chain(daycare)
+    }
+}
+
+impl SaveBlock2 {
+    pub fn from_bytes(data: &[u8]) -> LoadSaveResult<Self> {
+        let (raw_player_name, data) = data.split_at(PLAYER_NAME_LENGTH + 1);
+        let player_name = parse_string_lossy(raw_player_name);
+        let gender = Gender::try_from(data[0]).map_err(|i| {
+            LoadSaveError::CorruptData(format!("Invalid gender {}, expected 0 or 1", i))
+        })?;
+        let (_, data) = data.split_at(2); // gender and skip specialSaveWarpFlags
+        let (raw_trainer_id, data) = data.split_at(4);
+        let trainer_id = LittleEndian::read_u32(raw_trainer_id).into();
+
+        let hours = LittleEndian::read_u16(data) as u64;
+        let minutes = data[2] as u64;
+        let seconds = data[3] as u64;
+        let total_seconds = hours * 60 * 60 + minutes * 60 + seconds;
+        let play_time = Duration::from_secs(total_seconds);
+
+        Ok(SaveBlock2 {
+            player_name,
+            gender,
+            trainer_id,
+            play_time,
+        })
+    }
+}
+
+impl SaveBlock1 {
+    pub fn from_bytes(data: &[u8], layout: &Layout, key: u32) -> LoadSaveResult<Self> {
+        let location = Location::from_bytes(data);
+        let money = LittleEndian::read_u32(&data[layout.money..]) ^ key;
+        let coins = LittleEndian::read_u16(&data[layout.coins..]) ^ key as u16;
+        let pc_items = read_pocket(data, &layout.pc_items, 0);
+        let bag = layout
+            .bag
+            .as_ref()
+            .map(|bag_layout| Bag::from_bytes(data, bag_layout, key as u16));
+        let daycare = layout
+            .daycare
+            .map(|offset| DayCare::from_bytes(&data[offset..], layout.pokemon_format));
+        let roamer = layout
+            .roamer
+            .map(|offset| Roamer::from_bytes(&data[offset..]));
+        let game_stats = GameStats::from_bytes(
+            &data[layout.game_stats..layout.game_stats + layout.game_stats_count * 4],
+            key,
+        );
+        let link_battle_records = layout
+            .link_battle_records
+            .map(|offset| read_link_battle_records(&data[offset..]));
+        let flags = Flags::from_bytes(&data[layout.flags..layout.flags + layout.flags_size]);
+        let vars = Vars::from_bytes(&data[layout.vars..layout.vars + layout.vars_count * 2]);
+
+        let party_size = data[layout.party] as usize;
+        if party_size > PARTY_SIZE {
+            return Err(LoadSaveError::CorruptData(format!(
+                "Invalid party size {}, expected at most {}",
+                party_size, PARTY_SIZE
+            )));
+        }
+        let mut party = Vec::new();
+        for i in 0..party_size {
+            let offset = layout.party + 4 + i * Pokemon::SIZE;
+            party.push(Pokemon::from_bytes(
+                &data[offset..offset + Pokemon::SIZE],
+                layout.pokemon_format,
+            ));
+        }
+
+        Ok(SaveBlock1 {
+            location,
+            money,
+            coins,
+            bag,
+            pc_items,
+            daycare,
+            roamer,
+            game_stats,
+            link_battle_records,
+            flags,
+            vars,
+            party,
+        })
+    }
+}
+
+fn unsupported(field: &str) -> WriteSaveError {
+    WriteSaveError::InvalidData(format!("{} isn't supported for this game", field))
+}
+
+impl TryFrom<u8> for Gender {
+    type Error = u8;
+
+    fn try_from(raw: u8) -> Result<Self, u8> {
+        match raw {
+            0 => Ok(Gender::Male),
+            1 => Ok(Gender::Female),
+            other => Err(other),
+        }
+    }
+}
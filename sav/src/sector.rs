@@ -0,0 +1,137 @@
+use std::fmt::{self, Debug, Formatter};
+use std::io::{Read, Seek, SeekFrom, Write};
+
+use byteorder::{ByteOrder, LittleEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::{LoadSaveError, LoadSaveResult, WriteSaveError, WriteSaveResult};
+
+pub const SECTOR_SIZE: usize = 0x1000;
+pub const SECTOR_DATA_SIZE: usize = 0xff4;
+/// Value the game writes to the security field of every sector
+pub const SECTOR_SIGNATURE: u32 = 0x0801_2025;
+
+#[derive(Clone, Copy)]
+pub struct Sector {
+    data: [u8; SECTOR_DATA_SIZE],
+    pub id: u16,
+    checksum: u16,
+    pub security: u32,
+    pub counter: u32,
+}
+
+impl Debug for Sector {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("Sector")
+            .field("id", &self.id)
+            .field("counter", &self.counter)
+            .finish()
+    }
+}
+
+impl Sector {
+    /// Creates a sector with the checksum calculated over the first `checksum_size` bytes of data.
+    pub fn new(id: u16, data: [u8; SECTOR_DATA_SIZE], checksum_size: usize, counter: u32) -> Self {
+        Sector {
+            data,
+            id,
+            checksum: calculate_checksum(&data[..checksum_size]),
+            security: SECTOR_SIGNATURE,
+            counter,
+        }
+    }
+
+    pub fn data(&self) -> &[u8; SECTOR_DATA_SIZE] {
+        &self.data
+    }
+
+    pub fn move_to<R: Seek>(mut reader: R, index: u8) -> LoadSaveResult<()> {
+        reader
+            .seek(SeekFrom::Start((index as u64) * SECTOR_SIZE as u64))
+            .map_err(|e| LoadSaveError::io(format!("Sector {} not found", index), e))?;
+        Ok(())
+    }
+
+    pub fn read_at<R: Read + Seek>(mut reader: R, index: u8) -> LoadSaveResult<Self> {
+        Sector::move_to(&mut reader, index)?;
+        Sector::read(reader)
+    }
+
+    pub fn read<R: Read>(mut reader: R) -> LoadSaveResult<Self> {
+        let data = {
+            let mut buffer = [0u8; SECTOR_DATA_SIZE];
+            reader
+                .read_exact(&mut buffer)
+                .map_err(|e| LoadSaveError::io("Failed to read sector data", e))?;
+            buffer
+        };
+        let footer_err = |e| LoadSaveError::io("Failed to read sector footer", e);
+        let id = reader.read_u16::<LittleEndian>().map_err(footer_err)?;
+        let checksum = reader.read_u16::<LittleEndian>().map_err(footer_err)?;
+        let security = reader.read_u32::<LittleEndian>().map_err(footer_err)?;
+        let counter = reader.read_u32::<LittleEndian>().map_err(footer_err)?;
+
+        Ok(Sector {
+            data,
+            id,
+            checksum,
+            security,
+            counter,
+        })
+    }
+
+    pub fn write_at<W: Write + Seek>(&self, mut writer: W, index: u8) -> WriteSaveResult<()> {
+        writer
+            .seek(SeekFrom::Start((index as u64) * SECTOR_SIZE as u64))
+            .map_err(|e| WriteSaveError::io(format!("Sector {} not found", index), e))?;
+        self.write(writer)
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> WriteSaveResult<()> {
+        let write_err = |e| WriteSaveError::io("Failed to write sector", e);
+        writer.write_all(&self.data).map_err(write_err)?;
+        writer
+            .write_u16::<LittleEndian>(self.id)
+            .map_err(write_err)?;
+        writer
+            .write_u16::<LittleEndian>(self.checksum)
+            .map_err(write_err)?;
+        writer
+            .write_u32::<LittleEndian>(self.security)
+            .map_err(write_err)?;
+        writer
+            .write_u32::<LittleEndian>(self.counter)
+            .map_err(write_err)?;
+        Ok(())
+    }
+
+    pub fn validate_data(&self, size: usize) -> LoadSaveResult<&[u8]> {
+        let expected = calculate_checksum(&self.data[..size]);
+        if expected != self.checksum {
+            Err(LoadSaveError::InvalidChecksum {
+                id: self.id,
+                expected,
+                stored: self.checksum,
+            })
+        } else {
+            Ok(&self.data[..size])
+        }
+    }
+}
+
+fn calculate_checksum(data: &[u8]) -> u16 {
+    let mut checksum = 0u32;
+    let mut cursor: &[u8] = data;
+
+    assert!(
+        data.len() % 4 == 0,
+        "got data of size non-divisible by 4: {}",
+        data.len()
+    );
+
+    while !cursor.is_empty() {
+        checksum = checksum.wrapping_add(LittleEndian::read_u32(cursor));
+        cursor = &cursor[4..];
+    }
+
+    ((checksum >> 16) as u16).wrapping_add(checksum as u16)
+}
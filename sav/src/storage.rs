@@ -0,0 +1,115 @@
+use poke3_common::encoding::{parse_string_lossy, STRING_TERMINATOR};
+
+use crate::pokemon::{BoxPokemon, PokemonFormat};
+use crate::text::write_string;
+use crate::{LoadSaveError, LoadSaveResult, WriteSaveError, WriteSaveResult};
+
+pub const TOTAL_BOXES: usize = 14;
+pub const IN_BOX_COUNT: usize = 30;
+const BOX_NAME_LENGTH: usize = 8;
+
+pub const BOXES_OFFSET: usize = 0x0004;
+const BOX_NAMES_OFFSET: usize = BOXES_OFFSET + TOTAL_BOXES * IN_BOX_COUNT * BoxPokemon::SIZE;
+const WALLPAPERS_OFFSET: usize = BOX_NAMES_OFFSET + TOTAL_BOXES * (BOX_NAME_LENGTH + 1);
+
+/// Pokémon stored in the PC.
+#[derive(Clone, Debug)]
+pub struct PcStorage {
+    pub current_box: u8,
+    pub boxes: Vec<PcBox>,
+}
+
+#[derive(Clone, Debug)]
+pub struct PcBox {
+    pub name: String,
+    pub wallpaper: u8,
+    /// Every slot in the box, empty ones are `None`
+    pub pokemon: Vec<Option<BoxPokemon>>,
+}
+
+impl PcStorage {
+    pub fn from_bytes(data: &[u8], format: PokemonFormat) -> LoadSaveResult<Self> {
+        let current_box = data[0];
+        if current_box as usize >= TOTAL_BOXES {
+            return Err(LoadSaveError::CorruptData(format!(
+                "Invalid current box {}, expected at most {}",
+                current_box,
+                TOTAL_BOXES - 1
+            )));
+        }
+
+        let boxes = (0..TOTAL_BOXES)
+            .map(|i| {
+                let name_offset = BOX_NAMES_OFFSET + i * (BOX_NAME_LENGTH + 1);
+                let boxes_offset = BOXES_OFFSET + i * IN_BOX_COUNT * BoxPokemon::SIZE;
+                let pokemon = data[boxes_offset..boxes_offset + IN_BOX_COUNT * BoxPokemon::SIZE]
+                    .chunks(BoxPokemon::SIZE)
+                    .map(|raw| {
+                        if BoxPokemon::is_present(raw) {
+                            Some(BoxPokemon::from_bytes(raw, format))
+                        } else {
+                            None
+                        }
+                    })
+                    .collect();
+                PcBox {
+                    name: parse_string_lossy(&data[name_offset..name_offset + BOX_NAME_LENGTH + 1]),
+                    wallpaper: data[WALLPAPERS_OFFSET + i],
+                    pokemon,
+                }
+            })
+            .collect();
+
+        Ok(PcStorage { current_box, boxes })
+    }
+
+    pub fn write_bytes(&self, data: &mut [u8], format: PokemonFormat) -> WriteSaveResult<()> {
+        if self.boxes.len() != TOTAL_BOXES || self.current_box as usize >= TOTAL_BOXES {
+            return Err(WriteSaveError::InvalidData(format!(
+                "Expected {} PC boxes, current box {}",
+                TOTAL_BOXES, self.current_box
+            )));
+        }
+        data[0] = self.current_box;
+
+        for (i, pc_box) in self.boxes.iter().enumerate() {
+            if pc_box.pokemon.len() != IN_BOX_COUNT {
+                return Err(WriteSaveError::InvalidData(format!(
+                    "Expected {} slots in box {}, got {}",
+                    IN_BOX_COUNT,
+                    i + 1,
+                    pc_box.pokemon.len()
+                )));
+            }
+            let name_offset = BOX_NAMES_OFFSET + i * (BOX_NAME_LENGTH + 1);
+            let raw_name = &mut data[name_offset..name_offset + BOX_NAME_LENGTH + 1];
+            if parse_string_lossy(raw_name) != pc_box.name {
+                write_string(&mut raw_name[..BOX_NAME_LENGTH], &pc_box.name, "box name")?;
+                raw_name[BOX_NAME_LENGTH] = STRING_TERMINATOR;
+            }
+            data[WALLPAPERS_OFFSET + i] = pc_box.wallpaper;
+
+            let boxes_offset = BOXES_OFFSET + i * IN_BOX_COUNT * BoxPokemon::SIZE;
+            let slots = data[boxes_offset..boxes_offset + IN_BOX_COUNT * BoxPokemon::SIZE]
+                .chunks_mut(BoxPokemon::SIZE);
+            for (raw, slot) in slots.zip(&pc_box.pokemon) {
+                match slot {
+                    Some(pokemon) => pokemon.write_bytes(raw, format)?,
+                    None => raw.iter_mut().for_each(|b| *b = 0),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// All Pokémon in the PC along with their box and slot indices.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, &BoxPokemon)> {
+        self.boxes.iter().enumerate().flat_map(|(i, pc_box)| {
+            pc_box
+                .pokemon
+                .iter()
+                .enumerate()
+                .filter_map(move |(j, slot)| slot.as_ref().map(|p| (i, j, p)))
+        })
+    }
+}
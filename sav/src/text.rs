@@ -0,0 +1,12 @@
+use poke3_common::encoding::{encode_string, parse_string_lossy};
+
+use crate::{WriteSaveError, WriteSaveResult};
+
+/// Encodes a string into a fixed-size buffer, leaving the buffer untouched if it already decodes
+/// to the same string so that unchanged strings with characters we can't encode survive.
+pub fn write_string(buffer: &mut [u8], s: &str, field: &str) -> WriteSaveResult<()> {
+    if parse_string_lossy(buffer) == s {
+        return Ok(());
+    }
+    encode_string(s, buffer).map_err(|e| WriteSaveError::encoding(field, e))
+}
@@ -0,0 +1,44 @@
+use std::fmt::{self, Display, Formatter};
+
+/// A trainer's id, made of the public id shown on the trainer card and a secret id. Pokémon keep
+/// their original trainer's id.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct TrainerId {
+    /// Public id, shown in game
+    pub tid: u16,
+    /// Secret id, never shown in game
+    pub sid: u16,
+}
+
+impl TrainerId {
+    pub fn new(tid: u16, sid: u16) -> Self {
+        TrainerId { tid, sid }
+    }
+
+    /// Both ids as the single word the games store, used for shininess and Pokémon encryption.
+    pub fn value(self) -> u32 {
+        (self.sid as u32) << 16 | self.tid as u32
+    }
+}
+
+impl From<u32> for TrainerId {
+    fn from(value: u32) -> Self {
+        TrainerId {
+            tid: value as u16,
+            sid: (value >> 16) as u16,
+        }
+    }
+}
+
+impl From<TrainerId> for u32 {
+    fn from(id: TrainerId) -> Self {
+        id.value()
+    }
+}
+
+/// Formats as `TID/SID`, e.g. `12345/54321`.
+impl Display for TrainerId {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{:05}/{:05}", self.tid, self.sid)
+    }
+}
@@ -0,0 +1,168 @@
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::blocks::{checksum_size, SECTOR_CHUNK_SIZE};
+use crate::layout::{self, Layout};
+use crate::pokemon::{BoxPokemon, Pokemon, PokemonFormat};
+use crate::section::PARTY_SIZE;
+use crate::sector::Sector;
+use crate::storage::{BOXES_OFFSET, IN_BOX_COUNT, TOTAL_BOXES};
+use crate::{LoadSaveError, LoadSaveResult};
+
+/// Offset in SaveBlock2 of a word that's 0 in Ruby/Sapphire, 1 in FireRed/LeafGreen and the
+/// security key in Emerald.
+const GAME_CODE_OFFSET: usize = 0x00AC;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GameVersion {
+    RubySapphire,
+    Emerald,
+    FireRedLeafGreen,
+    /// FireRed hacks built with the Complete FireRed Upgrade engine, such as Radical Red
+    Cfru,
+}
+
+impl GameVersion {
+    pub fn layout(self) -> &'static Layout {
+        match self {
+            GameVersion::RubySapphire => &layout::RUBY_SAPPHIRE,
+            GameVersion::Emerald => &layout::EMERALD,
+            GameVersion::FireRedLeafGreen => &layout::FIRE_RED_LEAF_GREEN,
+            GameVersion::Cfru => &layout::CFRU,
+        }
+    }
+
+    /// Guesses the game a save slot comes from, given its sectors ordered by id.
+    pub fn detect(sectors: &[Sector]) -> LoadSaveResult<Self> {
+        let game_code = LittleEndian::read_u32(&sectors[0].data()[GAME_CODE_OFFSET..]);
+        let candidates: &[GameVersion] = match game_code {
+            0 => &[GameVersion::RubySapphire],
+            1 => &[GameVersion::FireRedLeafGreen, GameVersion::Cfru],
+            _ => &[GameVersion::Emerald],
+        };
+        // Sector checksums only cover the size of the structures for each game. If none match
+        // the slot is corrupt, report the mismatch for the game the code points to.
+        let mut checksum_error = None;
+        let candidates: Vec<_> = candidates
+            .iter()
+            .copied()
+            .filter(|version| match version.validate_checksums(sectors) {
+                Ok(()) => true,
+                Err(e) => {
+                    checksum_error.get_or_insert(e);
+                    false
+                }
+            })
+            .collect();
+
+        match candidates[..] {
+            [] => Err(checksum_error.expect("every candidate failed its checksums")),
+            [version] => Ok(version),
+            // Vanilla sectors are zeroed after the save structures, so CFRU sectors without extra
+            // data pass both checks. Check how Pokémon are stored instead, new games have none so
+            // fall back to vanilla for them.
+            [GameVersion::FireRedLeafGreen, GameVersion::Cfru] => {
+                match stored_pokemon_format(sectors) {
+                    Some(PokemonFormat::Cfru) => Ok(GameVersion::Cfru),
+                    Some(PokemonFormat::Vanilla) | None => Ok(GameVersion::FireRedLeafGreen),
+                }
+            }
+            _ => Err(LoadSaveError::UnknownGameVersion { candidates }),
+        }
+    }
+
+    fn validate_checksums(self, sectors: &[Sector]) -> LoadSaveResult<()> {
+        let layout = self.layout();
+        for (id, sector) in sectors.iter().enumerate() {
+            sector.validate_data(checksum_size(layout, id))?;
+        }
+        Ok(())
+    }
+}
+
+/// Format of the first party or PC Pokémon with a valid checksum in only one of the formats.
+/// Bad Eggs and Pokémon valid in both are skipped.
+fn stored_pokemon_format(sectors: &[Sector]) -> Option<PokemonFormat> {
+    // The party is at the start of SaveBlock1, so it's fully contained in the sector with id 1
+    let block1 = sectors[1].data();
+    let party_start = layout::FIRE_RED_LEAF_GREEN.party + 4;
+    let party = block1[party_start..party_start + PARTY_SIZE * Pokemon::SIZE]
+        .chunks(Pokemon::SIZE)
+        .map(|raw| &raw[..BoxPokemon::SIZE]);
+    let storage: Vec<u8> = sectors[5..]
+        .iter()
+        .flat_map(|sector| &sector.data()[..SECTOR_CHUNK_SIZE])
+        .copied()
+        .collect();
+    let pc = storage[BOXES_OFFSET..BOXES_OFFSET + TOTAL_BOXES * IN_BOX_COUNT * BoxPokemon::SIZE]
+        .chunks(BoxPokemon::SIZE);
+
+    party
+        .chain(pc)
+        .filter(|raw| BoxPokemon::is_present(raw))
+        .find_map(|raw| {
+            let valid = |format| BoxPokemon::from_bytes(raw, format).checksum.is_valid();
+            match (valid(PokemonFormat::Vanilla), valid(PokemonFormat::Cfru)) {
+                (true, false) => Some(PokemonFormat::Vanilla),
+                (false, true) => Some(PokemonFormat::Cfru),
+                _ => None,
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sector::SECTOR_DATA_SIZE;
+
+    /// Sectors of a FireRed/LeafGreen slot with the given SaveBlock1 sector data, checksums cover
+    /// the whole sector so they pass both the vanilla and CFRU checks.
+    fn fire_red_sectors(block1: [u8; SECTOR_DATA_SIZE]) -> Vec<Sector> {
+        (0..14)
+            .map(|id| {
+                let mut data = [0u8; SECTOR_DATA_SIZE];
+                match id {
+                    0 => data[GAME_CODE_OFFSET] = 1,
+                    1 => data = block1,
+                    _ => {}
+                }
+                Sector::new(id, data, SECTOR_DATA_SIZE, 1)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn detect_corrupt_sector() {
+        let mut sectors = fire_red_sectors([0; SECTOR_DATA_SIZE]);
+        let mut data = *sectors[3].data();
+        data[0x10] = 1;
+        sectors[3] = Sector::new(3, data, 0, 1);
+        match GameVersion::detect(&sectors) {
+            Err(LoadSaveError::InvalidChecksum { id: 3, .. }) => {}
+            other => panic!("expected a checksum error for sector 3, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn detect_fire_red_with_empty_party() {
+        let sectors = fire_red_sectors([0; SECTOR_DATA_SIZE]);
+        assert_eq!(
+            GameVersion::detect(&sectors).unwrap(),
+            GameVersion::FireRedLeafGreen
+        );
+    }
+
+    #[test]
+    fn detect_fire_red_with_bad_egg() {
+        let mut block1 = [0u8; SECTOR_DATA_SIZE];
+        let party = layout::FIRE_RED_LEAF_GREEN.party;
+        block1[party] = 1;
+        let raw = &mut block1[party + 4..party + 4 + BoxPokemon::SIZE];
+        raw.iter_mut().enumerate().for_each(|(i, b)| *b = i as u8);
+        raw[19] = 0b10;
+        let sectors = fire_red_sectors(block1);
+        assert_eq!(
+            GameVersion::detect(&sectors).unwrap(),
+            GameVersion::FireRedLeafGreen
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use std::io::Cursor;
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use poke3_sav::blocks::{checksum_size, SAVE_SECTION_SECTORS, SECTOR_CHUNK_SIZE};
+use poke3_sav::layout::FIRE_RED_LEAF_GREEN;
+use poke3_sav::pokemon::ChecksumStatus;
+use poke3_sav::sector::{Sector, SECTOR_DATA_SIZE, SECTOR_SIZE};
+use poke3_sav::{GameVersion, Save, SaveBlocks};
+
+const SECURITY_KEY: u32 = 0x1234_5678;
+const PERSONALITY: u32 = 5;
+const COUNTER: u32 = 7;
+const ROTATION: u8 = 3;
+
+/// A FireRed save with a single party Pokémon in slot 0, the other slot is left empty.
+fn fire_red_save() -> Vec<u8> {
+    let layout = &FIRE_RED_LEAF_GREEN;
+    let mut block2 = vec![0u8; layout.save_block_2_size];
+    block2[0xAC] = 1;
+    LittleEndian::write_u32(&mut block2[layout.security_key.unwrap()..], SECURITY_KEY);
+
+    let mut block1 = vec![0u8; layout.save_block_1_size];
+    LittleEndian::write_u32(&mut block1[layout.money..], 1000 ^ SECURITY_KEY);
+    block1[layout.party] = 1;
+    // A Pokémon with zeroed data, which encrypts to the key repeated
+    let pokemon = &mut block1[layout.party + 4..];
+    LittleEndian::write_u32(pokemon, PERSONALITY);
+    pokemon[19] = 0b10;
+    for word in pokemon[32..80].chunks_mut(4) {
+        LittleEndian::write_u32(word, PERSONALITY);
+    }
+
+    let storage = vec![0u8; 0x83D0];
+    let mut file = vec![0u8; 2 * SAVE_SECTION_SECTORS as usize * SECTOR_SIZE];
+    for id in 0..SAVE_SECTION_SECTORS as usize {
+        let (block, index) = match id {
+            0 => (&block2, 0),
+            1..=4 => (&block1, id - 1),
+            _ => (&storage, id - 5),
+        };
+        let start = index * SECTOR_CHUNK_SIZE;
+        let chunk = &block[start..block.len().min(start + SECTOR_CHUNK_SIZE)];
+        let mut data = [0u8; SECTOR_DATA_SIZE];
+        data[..chunk.len()].copy_from_slice(chunk);
+        let sector = Sector::new(id as u16, data, checksum_size(layout, id), COUNTER);
+        let position = (id as u8 + ROTATION) % SAVE_SECTION_SECTORS;
+        sector
+            .write_at(Cursor::new(&mut file[..]), position)
+            .unwrap();
+    }
+    file
+}
+
+#[test]
+fn write_round_trip() {
+    let mut file = fire_red_save();
+    let mut save = Save::read(Cursor::new(&file)).unwrap();
+    assert_eq!(save.version, GameVersion::FireRedLeafGreen);
+    assert_eq!(save.money, 1000);
+    assert_eq!(save.party[0].boxed.checksum, ChecksumStatus::Valid);
+
+    save.money = 2500;
+    save.party[0].boxed.experience = 1234;
+    save.write(Cursor::new(&mut file)).unwrap();
+
+    let blocks = SaveBlocks::read(Cursor::new(&file), None).unwrap();
+    assert_eq!(blocks.slot, 1);
+    assert_eq!(blocks.counter, COUNTER + 1);
+    assert_eq!(blocks.rotation, ROTATION + 1);
+
+    let save = Save::read(Cursor::new(&file)).unwrap();
+    assert_eq!(save.money, 2500);
+    assert_eq!(save.party[0].boxed.experience, 1234);
+    assert_eq!(save.party[0].boxed.checksum, ChecksumStatus::Valid);
+
+    // The loaded slot is left as a backup
+    let backup = SaveBlocks::read_slot(Cursor::new(&file), 0, None).unwrap();
+    assert_eq!(backup.counter, COUNTER);
+    assert_eq!(backup.rotation, ROTATION);
+    let backup = Save::from_blocks(&backup, &Default::default()).unwrap();
+    assert_eq!(backup.money, 1000);
+}